@@ -0,0 +1,84 @@
+use crate::solver::Part;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The table of known-good answers, one `year day part answer` row per line.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u16, u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        Answers::parse(&contents).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Parses the answers table. Blank lines and lines starting with `#`
+    /// are ignored.
+    pub fn parse(input: &str) -> Result<Answers, String> {
+        let mut answers = Answers::default();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                [year, day, part, answer] => {
+                    let key = (
+                        year.parse::<u16>()
+                            .map_err(|_| format!("line {}: invalid year '{}'", index + 1, year))?,
+                        day.parse::<u8>()
+                            .map_err(|_| format!("line {}: invalid day '{}'", index + 1, day))?,
+                        part.parse::<Part>()
+                            .map_err(|error| format!("line {}: {}", index + 1, error))?,
+                    );
+                    if answers.answers.insert(key, String::from(answer)).is_some() {
+                        return Err(format!("line {}: duplicate answer", index + 1));
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected \"<year> <day> <part> <answer>\"",
+                        index + 1
+                    ))
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let answers = Answers::parse("# comment\n2020 1 1 980499\n\n2020 10 2 abc\n").unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers.get(2020, 1, Part::One), Some("980499"));
+        assert_eq!(answers.get(2020, 10, Part::Two), Some("abc"));
+        assert_eq!(answers.get(2020, 1, Part::Two), None);
+
+        assert!(Answers::parse("2020 1 3 12").is_err());
+        assert!(Answers::parse("2020 1 1").is_err());
+        assert!(Answers::parse("2020 1 1 12\n2020 1 1 13").is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use crate::solver::{Day, Part};
+use crate::verify;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Where `bench --save` stores its results unless told otherwise.
+pub const BASELINE_FILE: &str = "bench-baseline.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    Generator,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    fn for_part(part: Part) -> Stage {
+        match part {
+            Part::One => Stage::Part1,
+            Part::Two => Stage::Part2,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Stage::Generator => "generator",
+            Stage::Part1 => "part 1",
+            Stage::Part2 => "part 2",
+        }
+    }
+}
+
+/// Summary statistics over a batch of samples, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Timing {
+    pub fn from_samples(samples: &mut [Duration]) -> Timing {
+        assert!(!samples.is_empty(), "no samples to summarize");
+        samples.sort_unstable();
+        let len = samples.len();
+        let median = if len % 2 == 1 {
+            samples[len / 2]
+        } else {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        };
+        Timing {
+            median_ns: median.as_nanos() as u64,
+            min_ns: samples[0].as_nanos() as u64,
+            max_ns: samples[len - 1].as_nanos() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub iterations: usize,
+    #[serde(flatten)]
+    pub timing: Timing,
+}
+
+/// A set of measurements saved to disk to compare later runs against.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        serde_json::from_str(&contents).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, contents + "\n").map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    fn find(&self, year: u16, day: u8, stage: Stage) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|m| m.year == year && m.day == day && m.stage == stage)
+    }
+}
+
+/// A stage whose median got slower than the baseline by more than the
+/// allowed threshold.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+    /// How much slower the current run is, in percent.
+    pub slowdown: f64,
+}
+
+/// A stage that could not be timed because it failed or panicked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Failure {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub message: String,
+}
+
+/// The results of benchmarking one day.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DayBench {
+    pub measurements: Vec<Measurement>,
+    pub failures: Vec<Failure>,
+}
+
+fn time<T>(iterations: usize, mut run: impl FnMut() -> T) -> Timing {
+    let mut samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(run());
+            start.elapsed()
+        })
+        .collect();
+    Timing::from_samples(&mut samples)
+}
+
+/// Times the generator and each solved part of `day` separately. A stage
+/// that returns an error or panics is reported as a failure instead, and
+/// parts are only timed once the generator succeeds.
+pub fn bench_day(day: &Day, input: &str, iterations: usize) -> DayBench {
+    let measurement = |stage: Stage, timing: Timing| Measurement {
+        year: day.year,
+        day: day.day,
+        stage,
+        iterations: iterations.max(1),
+        timing,
+    };
+    let failure = |stage: Stage, message: String| Failure {
+        year: day.year,
+        day: day.day,
+        stage,
+        message,
+    };
+    let panicked = |payload| format!("panicked: {}", verify::panic_message(payload));
+
+    let generator_failed = |message| DayBench {
+        measurements: Vec::new(),
+        failures: vec![failure(Stage::Generator, message)],
+    };
+
+    let parsed = match panic::catch_unwind(|| day.parse(input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(error)) => return generator_failed(error.to_string()),
+        Err(payload) => return generator_failed(panicked(payload)),
+    };
+    let mut bench = DayBench {
+        measurements: vec![measurement(
+            Stage::Generator,
+            time(iterations, || day.parse(black_box(input))),
+        )],
+        failures: Vec::new(),
+    };
+    for &part in Part::ALL.iter() {
+        let stage = Stage::for_part(part);
+        let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+            parsed
+                .solve(part)
+                .map(|answer| answer.is_some())
+                .map_err(|error| error.to_string())
+        }));
+        match solved {
+            Ok(Ok(true)) => {
+                let timing = time(iterations, || parsed.solve(part));
+                bench.measurements.push(measurement(stage, timing));
+            }
+            // Not solved yet, so there is nothing to time.
+            Ok(Ok(false)) => (),
+            Ok(Err(message)) => bench.failures.push(failure(stage, message)),
+            Err(payload) => bench.failures.push(failure(stage, panicked(payload))),
+        }
+    }
+    bench
+}
+
+/// Compares medians against `baseline` and returns every stage that got
+/// more than `threshold` percent slower.
+pub fn regressions(
+    baseline: &Baseline,
+    current: &[Measurement],
+    threshold: f64,
+) -> Vec<Regression> {
+    current
+        .iter()
+        .filter_map(|measurement| {
+            let previous = baseline.find(measurement.year, measurement.day, measurement.stage)?;
+            let baseline_ns = previous.timing.median_ns.max(1);
+            let current_ns = measurement.timing.median_ns;
+            let slowdown = (current_ns as f64 / baseline_ns as f64 - 1.0) * 100.0;
+            if slowdown > threshold {
+                Some(Regression {
+                    year: measurement.year,
+                    day: measurement.day,
+                    stage: measurement.stage,
+                    baseline_ns,
+                    current_ns,
+                    slowdown,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(day: u8, stage: Stage, median_ns: u64) -> Measurement {
+        Measurement {
+            year: 2020,
+            day,
+            stage,
+            iterations: 10,
+            timing: Timing {
+                median_ns,
+                min_ns: median_ns,
+                max_ns: median_ns,
+            },
+        }
+    }
+
+    #[test]
+    fn timing() {
+        let mut samples: Vec<Duration> = [5, 1, 4, 2]
+            .iter()
+            .map(|&n| Duration::from_nanos(n))
+            .collect();
+        let timing = Timing::from_samples(&mut samples);
+        assert_eq!(
+            timing,
+            Timing {
+                median_ns: 3,
+                min_ns: 1,
+                max_ns: 5
+            }
+        );
+
+        let day = crate::find_day(2020, 10).unwrap();
+        let bench = bench_day(&day, "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4", 3);
+        let stages: Vec<Stage> = bench.measurements.iter().map(|m| m.stage).collect();
+        assert_eq!(stages, vec![Stage::Generator, Stage::Part1, Stage::Part2]);
+        assert!(bench.failures.is_empty());
+        let bench = bench_day(&day, "16\nten", 3);
+        assert!(bench.measurements.is_empty());
+        assert_eq!(bench.failures[0].stage, Stage::Generator);
+
+        let day = crate::find_day(2020, 1).unwrap();
+        let bench = bench_day(&day, "1\n2", 2);
+        let stages: Vec<Stage> = bench.measurements.iter().map(|m| m.stage).collect();
+        assert_eq!(stages, vec![Stage::Generator]);
+        let failures: Vec<(Stage, &str)> = bench
+            .failures
+            .iter()
+            .map(|f| (f.stage, f.message.as_str()))
+            .collect();
+        assert_eq!(
+            failures,
+            vec![
+                (Stage::Part1, "no two entries add up to 2020"),
+                (Stage::Part2, "no three entries add up to 2020"),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let baseline = Baseline {
+            measurements: vec![
+                measurement(1, Stage::Part2, 1000),
+                measurement(9, Stage::Part2, 1000),
+            ],
+        };
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+
+        let current = vec![
+            measurement(1, Stage::Part2, 1500),
+            measurement(9, Stage::Part2, 1050),
+            measurement(9, Stage::Part1, 5000),
+        ];
+        let found = regressions(&baseline, &current, 10.0);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].day, found[0].stage), (1, Stage::Part2));
+        assert!((found[0].slowdown - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,797 @@
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::bench::{self, Baseline, Failure, Measurement, Regression, BASELINE_FILE};
+use crate::fetch::{FetchConfig, Fetcher, UreqClient};
+use crate::input::Source;
+use crate::solver::{Day, Part};
+use crate::verify::{self, Check, Status};
+use crate::y2020::day4::{self, Schema};
+use serde::Serialize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: advent_of_code <command> [options]
+
+Commands:
+  run       Solve one day, or every day with --all
+  verify    Check every day of a year against the answers table
+  bench     Time every generator and part, and compare with a baseline
+  fetch     Download missing inputs into input/<year>/ (needs AOC_SESSION)
+  help      Print this message
+
+Options for run:
+  --year <year>          Puzzle year (default: latest)
+  --day <day>            Day to solve
+  --part <1|2>           Only solve one part
+  --all                  Solve every registered day of the year
+  --input <path|->       Read the input from a file or stdin ('-')
+                         (default input/<year>/day<day>.txt)
+  --format <text|json>   Output format (default text)
+  --schema <path>        Passport rules for 2020 day 4 (default built in)
+
+Options for verify:
+  --year <year>          Puzzle year (default: latest)
+  --day <day>            Only verify one day
+  --answers <path>       Answers table (default answers.txt)
+  --format <text|json>   Output format (default text)
+
+Options for bench:
+  --year <year>          Puzzle year (default: latest)
+  --day <day>            Only benchmark one day
+  --iterations <n>       Samples per generator and part (default 50)
+  --baseline <path>      Baseline to compare with (default bench-baseline.json)
+  --threshold <percent>  Flag stages slower than the baseline by more than
+                         this (default 10)
+  --save                 Store the results in the baseline file
+  --format <text|json>   Output format (default text)
+
+Options for fetch:
+  --year <year>          Puzzle year (default: latest)
+  --day <day>            Only fetch one day (default every registered day)
+  --base-url <url>       Server to download from (default
+                         https://adventofcode.com)
+  --delay-ms <ms>        Minimum time between requests (default 3000)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    pub year: u16,
+    pub day: Option<u8>,
+    pub part: Option<Part>,
+    pub all: bool,
+    pub source: Source,
+    pub format: Format,
+    /// Passport rules to check 2020 day 4 against instead of the default.
+    pub schema: Option<PathBuf>,
+}
+
+impl Default for RunOptions {
+    fn default() -> RunOptions {
+        RunOptions {
+            year: crate::latest_year(),
+            day: None,
+            part: None,
+            all: false,
+            source: Source::Default,
+            format: Format::Text,
+            schema: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyOptions {
+    pub year: u16,
+    pub day: Option<u8>,
+    pub answers: PathBuf,
+    pub format: Format,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> VerifyOptions {
+        VerifyOptions {
+            year: crate::latest_year(),
+            day: None,
+            answers: PathBuf::from(ANSWERS_FILE),
+            format: Format::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    pub year: u16,
+    pub day: Option<u8>,
+    pub iterations: usize,
+    pub baseline: PathBuf,
+    pub threshold: f64,
+    pub save: bool,
+    pub format: Format,
+}
+
+impl Default for BenchOptions {
+    fn default() -> BenchOptions {
+        BenchOptions {
+            year: crate::latest_year(),
+            day: None,
+            iterations: 50,
+            baseline: PathBuf::from(BASELINE_FILE),
+            threshold: 10.0,
+            save: false,
+            format: Format::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FetchOptions {
+    pub year: Option<u16>,
+    pub day: Option<u8>,
+    pub base_url: Option<String>,
+    pub delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    Fetch(FetchOptions),
+    Help,
+}
+
+/// The result of solving a single part, or the reason it could not be
+/// solved. Errors that happen before a part runs (reading or parsing the
+/// input) have no part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Outcome {
+    pub year: u16,
+    pub day: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Outcome {
+    fn answer(day: &Day, part: Part, answer: String) -> Outcome {
+        Outcome {
+            year: day.year,
+            day: day.day,
+            part: Some(part.number()),
+            answer: Some(answer),
+            error: None,
+        }
+    }
+
+    fn error(day: &Day, part: Option<Part>, error: String) -> Outcome {
+        Outcome {
+            year: day.year,
+            day: day.day,
+            part: part.map(Part::number),
+            answer: None,
+            error: Some(error),
+        }
+    }
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => parse_run_options(args).map(Command::Run),
+        Some("verify") => parse_verify_options(args).map(Command::Verify),
+        Some("bench") => parse_bench_options(args).map(Command::Bench),
+        Some("fetch") => parse_fetch_options(args).map(Command::Fetch),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+fn parse_run_options<I: Iterator<Item = String>>(mut args: I) -> Result<RunOptions, String> {
+    let mut options = RunOptions::default();
+    let mut source = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => options.all = true,
+            "--year" => options.year = parse_value(&arg, args.next())?,
+            "--day" => options.day = Some(parse_value(&arg, args.next())?),
+            "--part" => options.part = Some(parse_value(&arg, args.next())?),
+            "--format" => options.format = parse_format(&value(&arg, args.next())?)?,
+            "--schema" => options.schema = Some(PathBuf::from(value(&arg, args.next())?)),
+            "--input" => {
+                source = Some(match value(&arg, args.next())?.as_str() {
+                    "-" => Source::Stdin,
+                    path => Source::File(PathBuf::from(path)),
+                })
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    match (options.all, options.day, &source) {
+        (true, Some(_), _) => return Err(String::from("--all cannot be combined with --day")),
+        (true, _, Some(_)) => return Err(String::from("--all cannot be combined with --input")),
+        (false, None, _) => return Err(String::from("either --day or --all is required")),
+        _ => (),
+    }
+    options.source = source.unwrap_or(Source::Default);
+    Ok(options)
+}
+
+fn parse_verify_options<I: Iterator<Item = String>>(mut args: I) -> Result<VerifyOptions, String> {
+    let mut options = VerifyOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_value(&arg, args.next())?,
+            "--day" => options.day = Some(parse_value(&arg, args.next())?),
+            "--answers" => options.answers = PathBuf::from(value(&arg, args.next())?),
+            "--format" => options.format = parse_format(&value(&arg, args.next())?)?,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_bench_options<I: Iterator<Item = String>>(mut args: I) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = parse_value(&arg, args.next())?,
+            "--day" => options.day = Some(parse_value(&arg, args.next())?),
+            "--iterations" => options.iterations = parse_value(&arg, args.next())?,
+            "--baseline" => options.baseline = PathBuf::from(value(&arg, args.next())?),
+            "--threshold" => options.threshold = parse_value(&arg, args.next())?,
+            "--save" => options.save = true,
+            "--format" => options.format = parse_format(&value(&arg, args.next())?)?,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    if options.iterations == 0 {
+        return Err(String::from("--iterations must be at least 1"));
+    }
+    Ok(options)
+}
+
+fn parse_fetch_options<I: Iterator<Item = String>>(mut args: I) -> Result<FetchOptions, String> {
+    let mut options = FetchOptions::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => options.year = Some(parse_value(&arg, args.next())?),
+            "--day" => options.day = Some(parse_value(&arg, args.next())?),
+            "--base-url" => options.base_url = Some(value(&arg, args.next())?),
+            "--delay-ms" => options.delay_ms = Some(parse_value(&arg, args.next())?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(options)
+}
+
+fn value(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} requires a value", flag))
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, input: Option<String>) -> Result<T, String> {
+    let input = value(flag, input)?;
+    input
+        .parse::<T>()
+        .map_err(|_| format!("invalid value '{}' for {}", input, flag))
+}
+
+fn parse_format(input: &str) -> Result<Format, String> {
+    match input {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("invalid format '{}', expected text or json", input)),
+    }
+}
+
+/// Returns the requested day of `year`, or every registered day of it.
+fn select_days(year: u16, day: Option<u8>) -> Result<Vec<Day>, String> {
+    match day {
+        Some(day) => crate::find_day(year, day)
+            .map(|found| vec![found])
+            .ok_or_else(|| format!("no solver for {} day {}", year, day)),
+        None => Ok(crate::registry()
+            .values()
+            .filter(|day| day.year == year)
+            .copied()
+            .collect()),
+    }
+}
+
+/// Solves the days and parts selected by `options`.
+pub fn run(options: &RunOptions) -> Result<Vec<Outcome>, String> {
+    let days = select_days(options.year, options.day)?;
+    let mut schema = match &options.schema {
+        Some(_) if (options.year, options.day) != (2020, Some(4)) => {
+            return Err(String::from("--schema only applies to 2020 day 4"))
+        }
+        Some(path) => Some(Schema::load(path)?),
+        None => None,
+    };
+
+    Ok(days
+        .iter()
+        .flat_map(|day| run_day(day, options.part, &options.source, schema.take()))
+        .collect())
+}
+
+fn run_day(day: &Day, part: Option<Part>, source: &Source, schema: Option<Schema>) -> Vec<Outcome> {
+    let input = match source.read(day.year, day.day) {
+        Ok(input) => input,
+        Err(error) => return vec![Outcome::error(day, None, error.to_string())],
+    };
+    let parsed = panic::catch_unwind(AssertUnwindSafe(|| match schema {
+        Some(schema) => day4::parse_with_schema(&input, schema),
+        None => day.parse(&input),
+    }));
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(error)) => return vec![Outcome::error(day, None, error.to_string())],
+        Err(payload) => {
+            let message = format!("panicked: {}", verify::panic_message(payload));
+            return vec![Outcome::error(day, None, message)];
+        }
+    };
+
+    // Returns `None` for a part that is not solved yet. Solvers report
+    // failures as errors; a panic is a bug, which the default hook prints,
+    // but it still only fails its own part.
+    let solve = |part: Part| {
+        let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+            parsed
+                .solve(part)
+                .map(|answer| answer.map(|answer| answer.to_string()))
+                .map_err(|error| error.to_string())
+        }));
+        match solved {
+            Ok(Ok(answer)) => answer.map(|answer| Outcome::answer(day, part, answer)),
+            Ok(Err(error)) => Some(Outcome::error(day, Some(part), error)),
+            Err(payload) => {
+                let message = format!("panicked: {}", verify::panic_message(payload));
+                Some(Outcome::error(day, Some(part), message))
+            }
+        }
+    };
+
+    match part {
+        Some(part) => vec![solve(part)
+            .unwrap_or_else(|| Outcome::error(day, Some(part), String::from("not solved yet")))],
+        None => Part::ALL.iter().filter_map(|&part| solve(part)).collect(),
+    }
+}
+
+fn print_text(outcomes: &[Outcome]) {
+    for outcome in outcomes {
+        let label = match outcome.part {
+            Some(part) => format!("Day {} - Part {}", outcome.day, part),
+            None => format!("Day {}", outcome.day),
+        };
+        match (&outcome.answer, &outcome.error) {
+            (Some(answer), _) => println!("{} : {}", label, answer),
+            (_, Some(error)) => eprintln!("{} : {}", label, error),
+            _ => (),
+        }
+    }
+}
+
+/// Checks the days selected by `options` against the answers table.
+pub fn verify(options: &VerifyOptions) -> Result<Vec<Check>, String> {
+    let answers = Answers::load(&options.answers)?;
+    let days = select_days(options.year, options.day)?;
+    Ok(days
+        .iter()
+        .flat_map(|day| verify::verify_day(day, &Source::Default, &answers))
+        .collect())
+}
+
+fn print_checks(checks: &[Check]) {
+    let count = |predicate: fn(&Status) -> bool| {
+        checks
+            .iter()
+            .filter(|check| predicate(&check.status))
+            .count()
+    };
+
+    for check in checks {
+        let result = match &check.status {
+            Status::Pass { answer } => format!("pass ({})", answer),
+            Status::Fail { expected, actual } => {
+                format!("FAIL (expected {}, got {})", expected, actual)
+            }
+            Status::MissingAnswer { actual } => format!("missing answer (got {})", actual),
+            Status::Panicked { message } => format!("PANICKED: {}", message),
+            Status::Error { message } => format!("ERROR: {}", message),
+        };
+        println!("Day {} - Part {} : {}", check.day, check.part, result);
+    }
+    println!(
+        "\n{} passed, {} failed, {} missing, {} panicked, {} errors",
+        count(|status| matches!(status, Status::Pass { .. })),
+        count(|status| matches!(status, Status::Fail { .. })),
+        count(|status| matches!(status, Status::MissingAnswer { .. })),
+        count(|status| matches!(status, Status::Panicked { .. })),
+        count(|status| matches!(status, Status::Error { .. })),
+    );
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub measurements: Vec<Measurement>,
+    pub regressions: Vec<Regression>,
+    /// Stages that returned an error or panicked instead of being timed.
+    pub failures: Vec<Failure>,
+}
+
+/// Benchmarks the days selected by `options`, compares them with the
+/// baseline if there is one and optionally saves them as the new baseline.
+pub fn bench(options: &BenchOptions) -> Result<BenchReport, String> {
+    let mut measurements = Vec::new();
+    let mut failures = Vec::new();
+    for day in select_days(options.year, options.day)? {
+        let input = Source::Default
+            .read(day.year, day.day)
+            .map_err(|error| error.to_string())?;
+        let timed = bench::bench_day(&day, &input, options.iterations);
+        measurements.extend(timed.measurements);
+        failures.extend(timed.failures);
+    }
+
+    let mut baseline = if options.baseline.exists() {
+        Baseline::load(&options.baseline)?
+    } else {
+        Baseline::default()
+    };
+    let regressions = bench::regressions(&baseline, &measurements, options.threshold);
+
+    if options.save {
+        baseline.measurements.retain(|old| {
+            !measurements
+                .iter()
+                .any(|new| (new.year, new.day, new.stage) == (old.year, old.day, old.stage))
+        });
+        baseline.measurements.extend(measurements.iter().cloned());
+        baseline
+            .measurements
+            .sort_by_key(|m| (m.year, m.day, m.stage));
+        baseline.save(&options.baseline)?;
+    }
+
+    Ok(BenchReport {
+        measurements,
+        regressions,
+        failures,
+    })
+}
+
+fn print_bench(report: &BenchReport) {
+    let nanos = Duration::from_nanos;
+    for m in &report.measurements {
+        let regression = report
+            .regressions
+            .iter()
+            .find(|r| (r.year, r.day, r.stage) == (m.year, m.day, m.stage));
+        let flag = match regression {
+            Some(r) => format!(
+                "  SLOWER by {:.1}% (baseline {:?})",
+                r.slowdown,
+                nanos(r.baseline_ns)
+            ),
+            None => String::new(),
+        };
+        println!(
+            "Day {} - {:<9} : median {:?} (min {:?}, max {:?}){}",
+            m.day,
+            m.stage.label(),
+            nanos(m.timing.median_ns),
+            nanos(m.timing.min_ns),
+            nanos(m.timing.max_ns),
+            flag
+        );
+    }
+    for f in &report.failures {
+        println!(
+            "Day {} - {:<9} : failed: {}",
+            f.day,
+            f.stage.label(),
+            f.message
+        );
+    }
+    if !report.regressions.is_empty() {
+        println!("\n{} regression(s) found", report.regressions.len());
+    }
+    if !report.failures.is_empty() {
+        println!("{} stage(s) failed", report.failures.len());
+    }
+}
+
+/// Entry point for the binary; returns the process exit code.
+pub fn main<I: IntoIterator<Item = String>>(args: I) -> i32 {
+    match parse_args(args) {
+        Ok(Command::Run(options)) => main_run(&options),
+        Ok(Command::Verify(options)) => main_verify(&options),
+        Ok(Command::Bench(options)) => main_bench(&options),
+        Ok(Command::Fetch(options)) => main_fetch(&options),
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            0
+        }
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            2
+        }
+    }
+}
+
+fn main_verify(options: &VerifyOptions) -> i32 {
+    let checks = match verify(options) {
+        Ok(checks) => checks,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return 1;
+        }
+    };
+    match options.format {
+        Format::Text => print_checks(&checks),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&checks).unwrap()),
+    }
+
+    if checks.iter().any(|check| check.status.is_failure()) {
+        1
+    } else {
+        0
+    }
+}
+
+fn main_bench(options: &BenchOptions) -> i32 {
+    let report = match bench(options) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return 1;
+        }
+    };
+    match options.format {
+        Format::Text => print_bench(&report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+    }
+
+    if report.regressions.is_empty() && report.failures.is_empty() {
+        0
+    } else {
+        1
+    }
+}
+
+fn main_fetch(options: &FetchOptions) -> i32 {
+    let mut config = match FetchConfig::from_env() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return 1;
+        }
+    };
+    if let Some(base_url) = &options.base_url {
+        config.base_url = base_url.clone();
+    }
+    if let Some(delay_ms) = options.delay_ms {
+        config.min_delay = Duration::from_millis(delay_ms);
+    }
+
+    let year = options.year.unwrap_or_else(crate::latest_year);
+    let days: Vec<u8> = match options.day {
+        Some(day) => vec![day],
+        None => crate::registry()
+            .values()
+            .filter(|day| day.year == year)
+            .map(|day| day.day)
+            .collect(),
+    };
+
+    let mut fetcher = Fetcher::new(UreqClient, config);
+    let mut status = 0;
+    for day in days {
+        let cached = fetcher.is_cached(year, day);
+        match fetcher.input(year, day) {
+            Ok(_) if cached => println!("Day {} : cached", day),
+            Ok(_) => println!(
+                "Day {} : downloaded {}",
+                day,
+                fetcher.path(year, day).display()
+            ),
+            Err(error) => {
+                eprintln!("Day {} : {}", day, error);
+                status = 1;
+            }
+        }
+    }
+    status
+}
+
+fn main_run(options: &RunOptions) -> i32 {
+    let outcomes = match run(options) {
+        Ok(outcomes) => outcomes,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return 1;
+        }
+    };
+    match options.format {
+        Format::Text => print_text(&outcomes),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&outcomes).unwrap()),
+    }
+
+    if outcomes.iter().any(|outcome| outcome.error.is_some()) {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parsing() {
+        let command = parse_args(args("run --day 8 --part 2 --input - --format json")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run(RunOptions {
+                day: Some(8),
+                part: Some(Part::Two),
+                source: Source::Stdin,
+                format: Format::Json,
+                ..RunOptions::default()
+            })
+        );
+        assert_eq!(parse_args(args("")).unwrap(), Command::Help);
+        assert!(parse_args(args("run")).is_err());
+        assert!(parse_args(args("run --all --day 1")).is_err());
+        assert!(parse_args(args("run --day 1 --part 3")).is_err());
+        assert!(parse_args(args("run --day")).is_err());
+        let command = parse_args(args("run --day 4 --schema rules.json")).unwrap();
+        assert_eq!(
+            command,
+            Command::Run(RunOptions {
+                day: Some(4),
+                schema: Some(PathBuf::from("rules.json")),
+                ..RunOptions::default()
+            })
+        );
+
+        let command = parse_args(args("verify --day 3 --answers other.txt")).unwrap();
+        assert_eq!(
+            command,
+            Command::Verify(VerifyOptions {
+                day: Some(3),
+                answers: PathBuf::from("other.txt"),
+                ..VerifyOptions::default()
+            })
+        );
+
+        let command = parse_args(args("bench --iterations 5 --threshold 2.5 --save")).unwrap();
+        assert_eq!(
+            command,
+            Command::Bench(BenchOptions {
+                iterations: 5,
+                threshold: 2.5,
+                save: true,
+                ..BenchOptions::default()
+            })
+        );
+        assert!(parse_args(args("bench --iterations 0")).is_err());
+
+        let command = parse_args(args("fetch --day 2 --base-url http://localhost:8000")).unwrap();
+        assert_eq!(
+            command,
+            Command::Fetch(FetchOptions {
+                day: Some(2),
+                base_url: Some(String::from("http://localhost:8000")),
+                ..FetchOptions::default()
+            })
+        );
+    }
+
+    #[test]
+    fn running() {
+        let options = RunOptions {
+            day: Some(1),
+            source: Source::File(PathBuf::from("input/2020/day1.txt")),
+            ..RunOptions::default()
+        };
+        let outcomes = run(&options).unwrap();
+        let answers: Vec<Option<String>> = outcomes.into_iter().map(|o| o.answer).collect();
+        assert_eq!(
+            answers,
+            vec![
+                Some(String::from("980499")),
+                Some(String::from("200637446"))
+            ]
+        );
+
+        let missing = RunOptions {
+            day: Some(2),
+            source: Source::File(PathBuf::from("input/2020/missing.txt")),
+            ..RunOptions::default()
+        };
+        let outcomes = run(&missing).unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].error.as_ref().unwrap().contains("missing.txt"));
+
+        let path = std::env::temp_dir().join(format!(
+            "advent_of_code-no-answer-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "1\n2\n").unwrap();
+        let unsolvable = RunOptions {
+            day: Some(1),
+            source: Source::File(path.clone()),
+            ..RunOptions::default()
+        };
+        let outcomes = run(&unsolvable).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let errors: Vec<Option<String>> = outcomes.into_iter().map(|o| o.error).collect();
+        assert_eq!(
+            errors,
+            vec![
+                Some(String::from("no two entries add up to 2020")),
+                Some(String::from("no three entries add up to 2020")),
+            ]
+        );
+        assert!(run(&RunOptions {
+            day: Some(26),
+            ..RunOptions::default()
+        })
+        .is_err());
+
+        let strict =
+            std::env::temp_dir().join(format!("advent_of_code-schema-{}.json", std::process::id()));
+        std::fs::write(
+            &strict,
+            r#"{"fields": [{"name": "pid", "required": true}]}"#,
+        )
+        .unwrap();
+        let path = std::env::temp_dir().join(format!(
+            "advent_of_code-passports-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, "pid:1\n\ncid:2\n\npid:3 cid:4\n").unwrap();
+        let custom = RunOptions {
+            day: Some(4),
+            source: Source::File(path.clone()),
+            schema: Some(strict.clone()),
+            ..RunOptions::default()
+        };
+        let outcomes = run(&custom).unwrap();
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(&strict).unwrap();
+        let answers: Vec<Option<String>> = outcomes.into_iter().map(|o| o.answer).collect();
+        assert_eq!(
+            answers,
+            vec![Some(String::from("2")), Some(String::from("1"))]
+        );
+        assert!(run(&custom).unwrap_err().contains("advent_of_code-schema"));
+        assert!(run(&RunOptions {
+            schema: Some(PathBuf::from("src/y2020/day4_rules.json")),
+            ..options
+        })
+        .is_err());
+    }
+}
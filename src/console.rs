@@ -0,0 +1,252 @@
+/// One instruction of the handheld game console.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Nop(i64),
+    Acc(i64),
+    Jmp(i64),
+}
+
+impl Instruction {
+    /// Swaps `jmp` and `nop`, the only kind of corruption the boot code can
+    /// have. `acc` cannot be repaired.
+    pub fn repaired(self) -> Option<Instruction> {
+        match self {
+            Instruction::Nop(arg) => Some(Instruction::Jmp(arg)),
+            Instruction::Jmp(arg) => Some(Instruction::Nop(arg)),
+            Instruction::Acc(_) => None,
+        }
+    }
+}
+
+/// Why a machine stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// The program counter moved to just past the last instruction.
+    Terminated { accumulator: i64 },
+    /// The instruction at `pc` was about to run for a second time.
+    Loop { accumulator: i64, pc: usize },
+    /// The program counter jumped somewhere other than the end of the
+    /// program.
+    OutOfBounds { accumulator: i64, pc: i64 },
+    /// The instruction at `pc` would have overflowed the accumulator or the
+    /// program counter.
+    Overflow { accumulator: i64, pc: usize },
+}
+
+impl Halt {
+    pub fn accumulator(self) -> i64 {
+        match self {
+            Halt::Terminated { accumulator }
+            | Halt::Loop { accumulator, .. }
+            | Halt::OutOfBounds { accumulator, .. }
+            | Halt::Overflow { accumulator, .. } => accumulator,
+        }
+    }
+}
+
+/// Executes a program one instruction at a time. Every instruction is
+/// allowed to run once, so any program halts after at most `len` steps.
+#[derive(Debug, Clone)]
+pub struct Machine<'a> {
+    program: &'a [Instruction],
+    patch: Option<(usize, Instruction)>,
+    pc: i64,
+    accumulator: i64,
+    visited: Vec<bool>,
+    halted: Option<Halt>,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(program: &'a [Instruction]) -> Machine<'a> {
+        Machine {
+            program,
+            patch: None,
+            pc: 0,
+            accumulator: 0,
+            visited: vec![false; program.len()],
+            halted: None,
+        }
+    }
+
+    /// Runs the program as if the instruction at `index` were replaced,
+    /// without copying the program.
+    pub fn patched(mut self, index: usize, instruction: Instruction) -> Machine<'a> {
+        self.patch = Some((index, instruction));
+        self
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    pub fn accumulator(&self) -> i64 {
+        self.accumulator
+    }
+
+    /// Indices of the instructions that have run so far.
+    pub fn executed(&self) -> impl Iterator<Item = usize> + '_ {
+        self.visited
+            .iter()
+            .enumerate()
+            .filter(|(_, &ran)| ran)
+            .map(|(index, _)| index)
+    }
+
+    fn instruction(&self, index: usize) -> Instruction {
+        match self.patch {
+            Some((patched, instruction)) if patched == index => instruction,
+            _ => self.program[index],
+        }
+    }
+
+    /// Executes a single instruction, or returns why the machine halted.
+    pub fn step(&mut self) -> Option<Halt> {
+        if self.halted.is_some() {
+            return self.halted;
+        }
+
+        let len = self.program.len() as i64;
+        let halt = if self.pc == len {
+            Some(Halt::Terminated {
+                accumulator: self.accumulator,
+            })
+        } else if self.pc < 0 || self.pc > len {
+            Some(Halt::OutOfBounds {
+                accumulator: self.accumulator,
+                pc: self.pc,
+            })
+        } else if self.visited[self.pc as usize] {
+            Some(Halt::Loop {
+                accumulator: self.accumulator,
+                pc: self.pc as usize,
+            })
+        } else {
+            None
+        };
+        if halt.is_some() {
+            self.halted = halt;
+            return halt;
+        }
+
+        let index = self.pc as usize;
+        self.visited[index] = true;
+        let (accumulator, pc) = match self.instruction(index) {
+            Instruction::Nop(_) => (Some(self.accumulator), self.pc.checked_add(1)),
+            Instruction::Acc(arg) => (self.accumulator.checked_add(arg), self.pc.checked_add(1)),
+            Instruction::Jmp(arg) => (Some(self.accumulator), self.pc.checked_add(arg)),
+        };
+        match (accumulator, pc) {
+            (Some(accumulator), Some(pc)) => {
+                self.accumulator = accumulator;
+                self.pc = pc;
+                None
+            }
+            _ => {
+                self.halted = Some(Halt::Overflow {
+                    accumulator: self.accumulator,
+                    pc: index,
+                });
+                self.halted
+            }
+        }
+    }
+
+    /// Runs until the machine halts.
+    pub fn run(&mut self) -> Halt {
+        loop {
+            if let Some(halt) = self.step() {
+                return halt;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Instruction::*;
+
+    #[test]
+    fn halting() {
+        let program = [
+            Nop(0),
+            Acc(1),
+            Jmp(4),
+            Acc(3),
+            Jmp(-3),
+            Acc(-99),
+            Acc(1),
+            Jmp(-4),
+            Acc(6),
+        ];
+        let mut machine = Machine::new(&program);
+        assert_eq!(machine.step(), None);
+        assert_eq!(machine.pc(), 1);
+        assert_eq!(
+            machine.run(),
+            Halt::Loop {
+                accumulator: 5,
+                pc: 1
+            }
+        );
+        assert_eq!(
+            machine.executed().collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 4, 6, 7]
+        );
+
+        let mut repaired = Machine::new(&program).patched(7, Nop(-4));
+        assert_eq!(repaired.run(), Halt::Terminated { accumulator: 8 });
+
+        let mut escaped = Machine::new(&program).patched(0, Jmp(-2));
+        assert_eq!(
+            escaped.run(),
+            Halt::OutOfBounds {
+                accumulator: 0,
+                pc: -2
+            }
+        );
+        assert_eq!(
+            escaped.step(),
+            Some(Halt::OutOfBounds {
+                accumulator: 0,
+                pc: -2
+            })
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let program = [Nop(0), Jmp(i64::MAX)];
+        let mut machine = Machine::new(&program);
+        assert_eq!(
+            machine.run(),
+            Halt::Overflow {
+                accumulator: 0,
+                pc: 1
+            }
+        );
+        assert_eq!(machine.pc(), 1);
+
+        let program = [Acc(i64::MAX), Acc(1)];
+        assert_eq!(
+            Machine::new(&program).run(),
+            Halt::Overflow {
+                accumulator: i64::MAX,
+                pc: 1
+            }
+        );
+    }
+
+    #[test]
+    fn long_programs() {
+        let mut program = vec![Acc(1); 1_000_000];
+        program.push(Jmp(-1_000_000));
+        assert_eq!(
+            Machine::new(&program).run(),
+            Halt::Loop {
+                accumulator: 1_000_000,
+                pc: 0
+            }
+        );
+    }
+}
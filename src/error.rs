@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Describes where and why a puzzle input could not be parsed.
+///
+/// Lines and columns are 1-based so they can be matched against an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "day {}: line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A single line of puzzle input, remembering its position so that errors
+/// can point back at it.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Builds an error pointing at `column` of this line.
+    pub fn error_at(&self, column: usize, expected: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, self.number, column, expected)
+    }
+
+    /// Builds an error pointing at the start of `part`, which must be a
+    /// slice of this line's text.
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.column_of(part), expected)
+    }
+
+    /// Returns the 1-based column at which `part` starts within this line.
+    pub fn column_of(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize).saturating_sub(start);
+        self.text[..offset.min(self.text.len())].chars().count() + 1
+    }
+
+    /// Parses `part` (a slice of this line) or reports what was expected.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse::<T>().map_err(|_| self.error(part, expected))
+    }
+}
+
+/// Splits `input` into numbered lines, ignoring trailing blank lines.
+pub fn lines(day: u8, input: &str) -> Result<Vec<Line<'_>>, ParseError> {
+    let lines: Vec<Line> = input
+        .trim_end()
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line {
+            day,
+            number: index + 1,
+            text,
+        })
+        .collect();
+
+    if lines.is_empty() {
+        Err(ParseError::new(day, 1, 1, "at least one line of input"))
+    } else {
+        Ok(lines)
+    }
+}
+
+/// Splits `input` into groups of lines separated by blank lines.
+pub fn groups(day: u8, input: &str) -> Result<Vec<Vec<Line<'_>>>, ParseError> {
+    let mut groups = vec![Vec::new()];
+    for line in lines(day, input)? {
+        if line.text.trim().is_empty() {
+            if !groups.last().unwrap().is_empty() {
+                groups.push(Vec::new());
+            }
+        } else {
+            groups.last_mut().unwrap().push(line);
+        }
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = ParseError::new(8, 12, 5, "a signed integer");
+        assert_eq!(
+            error.to_string(),
+            "day 8: line 12, column 5: expected a signed integer"
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let lines = lines(1, "12\n34 x\n\n").unwrap();
+        assert_eq!(lines.len(), 2);
+        let line = lines[1];
+        let error = line.parse::<u32>(&line.text[3..], "a number").unwrap_err();
+        assert_eq!(error, ParseError::new(1, 2, 4, "a number"));
+        assert!(super::lines(1, "\n\n").is_err());
+
+        let groups = groups(6, "ab\nc\n\n\nd\n").unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1][0].number, 5);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::input::{self, INPUT_DIR};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The site asks automated tools not to hammer it, so keep requests at
+/// least this far apart by default.
+pub const DEFAULT_MIN_DELAY: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = "github.com/sneako/advent_of_code_2020";
+
+#[derive(Debug)]
+pub enum FetchError {
+    MissingSession,
+    Status { url: String, status: u16 },
+    Transport { url: String, message: String },
+    Io(io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::MissingSession => write!(f, "{} is not set", SESSION_VAR),
+            FetchError::Status { url, status } => write!(f, "{}: HTTP status {}", url, status),
+            FetchError::Transport { url, message } => write!(f, "{}: {}", url, message),
+            FetchError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> FetchError {
+        FetchError::Io(error)
+    }
+}
+
+/// The HTTP backend used to download inputs, so tests can swap it out.
+pub trait HttpClient {
+    fn get(&self, url: &str, session: &str) -> Result<String, FetchError>;
+}
+
+/// Downloads over HTTP(S) with `ureq`.
+pub struct UreqClient;
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<String, FetchError> {
+        let response = ureq::get(url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|error| match error {
+                ureq::Error::Status(status, _) => FetchError::Status {
+                    url: String::from(url),
+                    status,
+                },
+                ureq::Error::Transport(transport) => FetchError::Transport {
+                    url: String::from(url),
+                    message: transport.to_string(),
+                },
+            })?;
+        Ok(response.into_string()?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchConfig {
+    pub base_url: String,
+    pub session: String,
+    pub input_dir: PathBuf,
+    pub min_delay: Duration,
+}
+
+impl FetchConfig {
+    /// Default settings, with the session read from `AOC_SESSION`.
+    pub fn from_env() -> Result<FetchConfig, FetchError> {
+        match std::env::var(SESSION_VAR) {
+            Ok(session) if !session.trim().is_empty() => Ok(FetchConfig {
+                base_url: String::from(DEFAULT_BASE_URL),
+                session: String::from(session.trim()),
+                input_dir: PathBuf::from(INPUT_DIR),
+                min_delay: DEFAULT_MIN_DELAY,
+            }),
+            _ => Err(FetchError::MissingSession),
+        }
+    }
+}
+
+/// Downloads puzzle inputs into the `input/<year>/day<N>.txt` cache,
+/// only hitting the network for inputs that are not cached yet.
+pub struct Fetcher<C: HttpClient> {
+    client: C,
+    config: FetchConfig,
+    last_request: Option<Instant>,
+}
+
+impl<C: HttpClient> Fetcher<C> {
+    pub fn new(client: C, config: FetchConfig) -> Fetcher<C> {
+        Fetcher {
+            client,
+            config,
+            last_request: None,
+        }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        input::path_in(&self.config.input_dir, year, day)
+    }
+
+    pub fn is_cached(&self, year: u16, day: u8) -> bool {
+        self.path(year, day).exists()
+    }
+
+    /// Returns the input for a puzzle, downloading it first if needed.
+    pub fn input(&mut self, year: u16, day: u8) -> Result<String, FetchError> {
+        let path = self.path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        self.wait_for_slot();
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.config.base_url.trim_end_matches('/'),
+            year,
+            day
+        );
+        let result = self.client.get(&url, &self.config.session);
+        self.last_request = Some(Instant::now());
+        let input = result?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    fn wait_for_slot(&self) {
+        if let Some(last) = self.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.config.min_delay {
+                thread::sleep(self.config.min_delay - elapsed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+
+    struct Recording {
+        requests: RefCell<Vec<(String, Instant)>>,
+    }
+
+    impl HttpClient for &Recording {
+        fn get(&self, url: &str, _session: &str) -> Result<String, FetchError> {
+            self.requests
+                .borrow_mut()
+                .push((String::from(url), Instant::now()));
+            Ok(String::from("1\n2\n3\n"))
+        }
+    }
+
+    fn config(name: &str, base_url: &str, min_delay: Duration) -> FetchConfig {
+        let input_dir =
+            std::env::temp_dir().join(format!("advent_of_code-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&input_dir);
+        FetchConfig {
+            base_url: String::from(base_url),
+            session: String::from("secret"),
+            input_dir,
+            min_delay,
+        }
+    }
+
+    #[test]
+    fn caches_and_spaces_requests() {
+        let recording = Recording {
+            requests: RefCell::new(Vec::new()),
+        };
+        let config = config("cache", "http://example.test/", Duration::from_millis(50));
+        let input_dir = config.input_dir.clone();
+        let mut fetcher = Fetcher::new(&recording, config);
+
+        assert!(!fetcher.is_cached(2020, 1));
+        assert_eq!(fetcher.input(2020, 1).unwrap(), "1\n2\n3\n");
+        assert!(fetcher.is_cached(2020, 1));
+        assert_eq!(fetcher.input(2020, 1).unwrap(), "1\n2\n3\n");
+        fetcher.input(2020, 2).unwrap();
+
+        let requests = recording.requests.borrow();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].0, "http://example.test/2020/day/1/input");
+        assert!(requests[1].1 - requests[0].1 >= Duration::from_millis(50));
+        assert_eq!(
+            fs::read_to_string(input::path_in(&input_dir, 2020, 2)).unwrap(),
+            "1\n2\n3\n"
+        );
+        fs::remove_dir_all(input_dir).unwrap();
+    }
+
+    #[test]
+    fn downloads_from_local_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let mut responses = vec![
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\n+1\n-2\n",
+            ];
+            let mut requests = Vec::new();
+            while let Some(response) = responses.pop() {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push(String::from(line.trim()));
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let config = config(
+            "server",
+            &format!("http://{}", address),
+            Duration::from_millis(0),
+        );
+        let input_dir = config.input_dir.clone();
+        let mut fetcher = Fetcher::new(UreqClient, config);
+        assert_eq!(fetcher.input(2020, 8).unwrap(), "+1\n-2\n");
+        match fetcher.input(2020, 9) {
+            Err(FetchError::Status { status: 404, .. }) => (),
+            other => panic!("expected a 404, got {:?}", other),
+        }
+        assert!(!Path::new(&input::path_in(&input_dir, 2020, 9)).exists());
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0][0], "GET /2020/day/8/input HTTP/1.1");
+        assert!(requests[0]
+            .iter()
+            .any(|header| header == "Cookie: session=secret"));
+        fs::remove_dir_all(input_dir).unwrap();
+    }
+}
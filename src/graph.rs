@@ -0,0 +1,313 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+/// Handle for a node interned in a [`Graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+/// Returned when a graph that must be acyclic contains a cycle. The nodes
+/// are listed in edge order and the first node is repeated at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    pub cycle: Vec<N>,
+}
+
+impl<N: Display> Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.cycle.iter().map(|node| node.to_string()).collect();
+        write!(f, "cycle detected: {}", names.join(" -> "))
+    }
+}
+
+impl<N: Debug + Display> Error for CycleError<N> {}
+
+/// A directed graph with weighted edges. Nodes are interned so they can be
+/// referred to by a cheap [`NodeId`], and edges are indexed in both
+/// directions so predecessors are as cheap to find as successors.
+#[derive(Debug, Clone)]
+pub struct Graph<N, W> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    forward: Vec<Vec<(NodeId, W)>>,
+    reverse: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<N: Clone + Eq + Hash, W: Clone> Default for Graph<N, W> {
+    fn default() -> Graph<N, W> {
+        Graph::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash, W: Clone> Graph<N, W> {
+    pub fn new() -> Graph<N, W> {
+        Graph {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            forward: Vec::new(),
+            reverse: Vec::new(),
+        }
+    }
+
+    /// Returns the id of `node`, adding it to the graph if it is new.
+    pub fn intern(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        self.forward.push(Vec::new());
+        self.reverse.push(Vec::new());
+        id
+    }
+
+    pub fn id<Q>(&self, node: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(node).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id.0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    /// Adds a weighted edge, interning both ends.
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        self.forward[from.0].push((to, weight.clone()));
+        self.reverse[to.0].push((from, weight));
+        (from, to)
+    }
+
+    /// Outgoing edges of `id`, in insertion order.
+    pub fn successors(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.forward[id.0]
+    }
+
+    /// Incoming edges of `id`, in insertion order.
+    pub fn predecessors(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.reverse[id.0]
+    }
+
+    /// Every node reachable from `start` by following edges forwards,
+    /// not counting `start` itself unless it is on a cycle.
+    pub fn reachable_from(&self, start: NodeId) -> Vec<NodeId> {
+        search(start, &self.forward)
+    }
+
+    /// Every node from which `target` can be reached.
+    pub fn ancestors(&self, target: NodeId) -> Vec<NodeId> {
+        search(target, &self.reverse)
+    }
+
+    /// Orders the nodes so that every edge points from an earlier node to
+    /// a later one, or reports a cycle.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, CycleError<N>> {
+        let mut order = self.post_order(self.ids(), |_| false)?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Lists every node reachable from `roots`, each one after all of its
+    /// successors, or reports the first cycle found. Nodes for which `done`
+    /// is true are left out along with everything beyond them, so callers
+    /// can skip work they have already finished.
+    pub fn post_order<I, F>(&self, roots: I, done: F) -> Result<Vec<NodeId>, CycleError<N>>
+    where
+        I: IntoIterator<Item = NodeId>,
+        F: Fn(NodeId) -> bool,
+    {
+        self.post_order_along(&self.forward, roots, done)
+    }
+
+    /// Like [`Graph::post_order`], but follows edges backwards: every node
+    /// that can reach `roots` is listed after all of its predecessors.
+    pub fn ancestors_post_order<I, F>(
+        &self,
+        roots: I,
+        done: F,
+    ) -> Result<Vec<NodeId>, CycleError<N>>
+    where
+        I: IntoIterator<Item = NodeId>,
+        F: Fn(NodeId) -> bool,
+    {
+        self.post_order_along(&self.reverse, roots, done)
+            .map_err(|mut error| {
+                // Found against the edges, so put it back in edge order.
+                error.cycle.reverse();
+                error
+            })
+    }
+
+    fn post_order_along<I, F>(
+        &self,
+        edges: &[Vec<(NodeId, W)>],
+        roots: I,
+        done: F,
+    ) -> Result<Vec<NodeId>, CycleError<N>>
+    where
+        I: IntoIterator<Item = NodeId>,
+        F: Fn(NodeId) -> bool,
+    {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Active,
+            Done,
+        }
+
+        let mut marks = vec![Mark::New; self.nodes.len()];
+        let mut order = Vec::new();
+
+        for root in roots {
+            if marks[root.0] != Mark::New || done(root) {
+                continue;
+            }
+            // Each frame is a node and how many of its edges were visited.
+            let mut stack = vec![(root, 0)];
+            marks[root.0] = Mark::Active;
+
+            while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+                match edges[node.0].get(*next) {
+                    Some(&(child, _)) => {
+                        *next += 1;
+                        match marks[child.0] {
+                            Mark::New if done(child) => marks[child.0] = Mark::Done,
+                            Mark::New => {
+                                marks[child.0] = Mark::Active;
+                                stack.push((child, 0));
+                            }
+                            Mark::Active => {
+                                let start = stack.iter().position(|&(n, _)| n == child).unwrap();
+                                let mut cycle: Vec<N> = stack[start..]
+                                    .iter()
+                                    .map(|&(n, _)| self.nodes[n.0].clone())
+                                    .collect();
+                                cycle.push(self.nodes[child.0].clone());
+                                return Err(CycleError { cycle });
+                            }
+                            Mark::Done => (),
+                        }
+                    }
+                    None => {
+                        marks[node.0] = Mark::Done;
+                        order.push(node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Returns the first cycle found, if any.
+    pub fn find_cycle(&self) -> Result<(), CycleError<N>> {
+        self.topological_sort().map(|_| ())
+    }
+}
+
+fn search<W>(start: NodeId, edges: &[Vec<(NodeId, W)>]) -> Vec<NodeId> {
+    let mut seen = vec![false; edges.len()];
+    let mut found = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        for &(next, _) in &edges[node.0] {
+            if !seen[next.0] {
+                seen[next.0] = true;
+                found.push(next);
+                stack.push(next);
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str, u32)]) -> Graph<&'static str, u32> {
+        let mut graph = Graph::new();
+        for &(from, to, weight) in edges {
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    fn names(graph: &Graph<&'static str, u32>, ids: Vec<NodeId>) -> Vec<&'static str> {
+        let mut names: Vec<&str> = ids.into_iter().map(|id| *graph.node(id)).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn queries() {
+        let graph = graph(&[("a", "b", 1), ("a", "c", 2), ("b", "d", 3), ("c", "d", 4)]);
+        let a = graph.id("a").unwrap();
+        let d = graph.id("d").unwrap();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.id("e"), None);
+        assert_eq!(graph.successors(a).len(), 2);
+        assert_eq!(graph.predecessors(d)[1], (graph.id("c").unwrap(), 4));
+        assert_eq!(names(&graph, graph.reachable_from(a)), vec!["b", "c", "d"]);
+        assert_eq!(names(&graph, graph.ancestors(d)), vec!["a", "b", "c"]);
+
+        let order: Vec<&str> = graph
+            .topological_sort()
+            .unwrap()
+            .into_iter()
+            .map(|id| *graph.node(id))
+            .collect();
+        assert_eq!(order.first(), Some(&"a"));
+        assert_eq!(order.last(), Some(&"d"));
+
+        let b = graph.id("b").unwrap();
+        let order = graph.post_order(Some(a), |node| node == b).unwrap();
+        let order: Vec<&str> = order.into_iter().map(|id| *graph.node(id)).collect();
+        assert_eq!(order, vec!["d", "c", "a"]);
+        let order = graph.ancestors_post_order(Some(d), |_| false).unwrap();
+        assert_eq!(order.last(), Some(&d));
+        assert_eq!(order.first(), Some(&a));
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 1), ("c", "b", 1)]);
+        let error = graph.topological_sort().unwrap_err();
+        assert_eq!(error.cycle, vec!["b", "c", "b"]);
+        assert_eq!(error.to_string(), "cycle detected: b -> c -> b");
+        assert!(graph.find_cycle().is_err());
+        let c = graph.id("c").unwrap();
+        let error = graph.ancestors_post_order(Some(c), |_| false).unwrap_err();
+        assert_eq!(error.cycle, vec!["c", "b", "c"]);
+
+        let b = graph.id("b").unwrap();
+        assert_eq!(names(&graph, graph.reachable_from(b)), vec!["b", "c"]);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::error::{lines, ParseError};
+
+/// Offsets of the orthogonal neighbours of a cell, as `(dx, dy)`.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the orthogonal and diagonal neighbours of a cell.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular map of cells addressed by `(x, y)`, with `(0, 0)` in the
+/// top left corner. Either axis can wrap around, for maps that repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    wrap_horizontal: bool,
+    wrap_vertical: bool,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    ///
+    /// Panics if `cells` does not hold exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), width * height, "grid size mismatch");
+        Grid {
+            cells,
+            width,
+            height,
+            wrap_horizontal: false,
+            wrap_vertical: false,
+        }
+    }
+
+    /// Parses a character map, turning each character into a cell with
+    /// `cell`. Every row must be as wide as the first one.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let lines = lines(day, input)?;
+        let width = lines[0].text.chars().count();
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for line in &lines {
+            let mut row_width = 0;
+            for (index, c) in line.text.chars().enumerate() {
+                if index == width {
+                    return Err(line.error_at(index + 1, format!("a row of width {}", width)));
+                }
+                cells.push(cell(c).ok_or_else(|| line.error_at(index + 1, expected))?);
+                row_width += 1;
+            }
+            if row_width < width {
+                return Err(line.error_at(row_width + 1, format!("a row of width {}", width)));
+            }
+        }
+
+        Ok(Grid::new(width, lines.len(), cells))
+    }
+
+    /// Sets which axes wrap around when a coordinate falls off the edge.
+    pub fn with_wrapping(mut self, horizontal: bool, vertical: bool) -> Grid<T> {
+        self.wrap_horizontal = horizontal;
+        self.wrap_vertical = vertical;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Maps a possibly out-of-range position onto the grid, applying
+    /// wrapping. Returns `None` if the position is off a non-wrapping edge.
+    pub fn resolve(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        let x = resolve_axis(x, self.width, self.wrap_horizontal)?;
+        let y = resolve_axis(y, self.height, self.wrap_vertical)?;
+        Some((x, y))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        let (x, y) = self.resolve(x as isize, y as isize)?;
+        self.cells.get(y * self.width + x)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        let (x, y) = self.resolve(x as isize, y as isize)?;
+        self.cells.get_mut(y * self.width + x)
+    }
+
+    /// Iterates over the cells at `offsets` from `(x, y)` that are on the
+    /// grid, along with their positions.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = self.resolve(x as isize + dx, y as isize + dy)?;
+            Some(((nx, ny), &self.cells[ny * self.width + nx]))
+        })
+    }
+
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours(x, y, &NEIGHBOURS_4)
+    }
+
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours(x, y, &NEIGHBOURS_8)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        if x < self.width {
+            Some(self.cells.iter().skip(x).step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// Iterates over every cell and its position, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index % width, index / width), cell))
+    }
+
+    /// Renders the grid back to text, one line per row.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            output.extend(row.iter().map(&cell));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+fn resolve_axis(position: isize, len: usize, wrap: bool) -> Option<usize> {
+    if len == 0 {
+        None
+    } else if wrap {
+        Some(position.rem_euclid(len as isize) as usize)
+    } else if position >= 0 && (position as usize) < len {
+        Some(position as usize)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(0, input, "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn access_and_wrapping() {
+        let grid = digits("123\n456\n");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+
+        let wrapped = grid.clone().with_wrapping(true, false);
+        assert_eq!(wrapped.get(3, 0), Some(&1));
+        assert_eq!(wrapped.get(7, 1), Some(&5));
+        assert_eq!(wrapped.get(0, 2), None);
+        assert_eq!(wrapped.resolve(-1, 1), Some((2, 1)));
+
+        let error = Grid::parse(0, "123\n4x6", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = Grid::parse(0, "123\n4567", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(error.expected, "a row of width 3");
+    }
+
+    #[test]
+    fn neighbours_and_iterators() {
+        let grid = digits("123\n456\n789");
+        let around: Vec<u32> = grid.neighbours4(0, 0).map(|(_, &c)| c).collect();
+        assert_eq!(around, vec![2, 4]);
+        let around: Vec<u32> = grid.neighbours8(1, 1).map(|(_, &c)| c).collect();
+        assert_eq!(around, vec![1, 2, 3, 4, 6, 7, 8, 9]);
+
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        let column: Vec<u32> = grid.column(2).unwrap().copied().collect();
+        assert_eq!(column, vec![3, 6, 9]);
+        assert_eq!(grid.columns().count(), 3);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(
+            grid.render(|&c| std::char::from_digit(c, 10).unwrap()),
+            "123\n456\n789\n"
+        );
+    }
+}
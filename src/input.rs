@@ -0,0 +1,44 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Directory that holds puzzle inputs, laid out as `input/<year>/day<N>.txt`.
+pub const INPUT_DIR: &str = "input";
+
+/// Returns the conventional location of a puzzle input.
+pub fn path(year: u16, day: u8) -> PathBuf {
+    path_in(Path::new(INPUT_DIR), year, day)
+}
+
+/// Returns the location of a puzzle input under another input directory.
+pub fn path_in(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{}.txt", day))
+}
+
+/// Where a runner should read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// The conventional `input/<year>/day<N>.txt` file.
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn read(&self, year: u16, day: u8) -> io::Result<String> {
+        match self {
+            Source::Default => read_file(&path(year, day)),
+            Source::File(path) => read_file(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+fn read_file(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|error| io::Error::new(error.kind(), format!("{}: {}", path.display(), error)))
+}
@@ -1,18 +1,86 @@
-extern crate aoc_runner;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod console;
+pub mod error;
+pub mod fetch;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod solver;
+pub mod verify;
 
-#[macro_use]
-extern crate aoc_runner_derive;
+pub mod y2020;
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
+use solver::Day;
+use std::collections::BTreeMap;
 
-aoc_lib! { year = 2020 }
+/// Every solved puzzle, keyed by year and day. Each year lives in its own
+/// `y<year>` module and lists its days there.
+pub fn registry() -> BTreeMap<(u16, u8), Day> {
+    y2020::days()
+        .into_iter()
+        .map(|day| ((day.year, day.day), day))
+        .collect()
+}
 
+/// The most recent year with at least one solved day.
+pub fn latest_year() -> u16 {
+    registry()
+        .keys()
+        .map(|&(year, _)| year)
+        .max()
+        .unwrap_or(y2020::YEAR)
+}
+
+/// Looks up the solver for a single puzzle.
+pub fn find_day(year: u16, day: u8) -> Option<Day> {
+    registry().get(&(year, day)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solver::Part;
+
+    #[test]
+    fn registry() {
+        let days: Vec<u8> = super::registry().values().map(|day| day.day).collect();
+        assert_eq!(days, (1..=10).collect::<Vec<u8>>());
+
+        let day1 = find_day(2020, 1).unwrap();
+        let parsed = day1.parse("1721\n979\n366\n299\n675\n1456").unwrap();
+        assert_eq!(parsed.solve(Part::One).unwrap().unwrap().to_string(), "514579");
+        assert_eq!(parsed.solve(Part::Two).unwrap().unwrap().to_string(), "241861950");
+        assert!(find_day(2020, 25).is_none());
+        assert!(find_day(2019, 1).is_none());
+        assert_eq!(latest_year(), 2020);
+    }
+
+    #[test]
+    fn parts_report_missing_answers() {
+        // Well-formed inputs on which some parts find no answer: every part
+        // has to say so through its result rather than by panicking.
+        let inputs = [
+            (1, "1\n2"),
+            (2, "1-3 a: bcd"),
+            (3, "."),
+            (4, "ecl:gry"),
+            (5, "FBFBBFFRLR"),
+            (6, "a"),
+            (7, "light red bags contain no other bags."),
+            (8, "acc +1\njmp -1\nacc +1\njmp -1"),
+            (9, "1\n2"),
+            (10, "1"),
+        ];
+        assert_eq!(inputs.len(), super::registry().len());
+        for (day, input) in inputs {
+            let parsed = find_day(2020, day).unwrap().parse(input).unwrap();
+            for part in [Part::One, Part::Two] {
+                if let Err(error) = parsed.solve(part) {
+                    assert!(!error.to_string().is_empty());
+                }
+            }
+        }
+    }
+}
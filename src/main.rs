@@ -1,8 +1,8 @@
 extern crate advent_of_code;
-extern crate aoc_runner_derive;
-extern crate aoc_runner;
-extern crate regex;
 
-use aoc_runner_derive::aoc_main;
+use std::env;
+use std::process;
 
-aoc_main! { lib = advent_of_code }
+fn main() {
+    process::exit(advent_of_code::cli::main(env::args().skip(1)));
+}
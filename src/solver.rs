@@ -0,0 +1,132 @@
+use crate::error::ParseError;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// The result of a single part, as printed by the runners.
+pub type Answer = Box<dyn Display>;
+
+/// What running a part produces: its answer, `None` if the part is not
+/// solved yet, or the reason the input has no answer.
+pub type Solution = Result<Option<Answer>, Box<dyn Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Part, String> {
+        match input {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part '{}', expected 1 or 2", input)),
+        }
+    }
+}
+
+/// A day's puzzle: a generator and the two parts that run on its output.
+///
+/// A part that has not been solved yet returns `Ok(None)`; a part that
+/// finds no answer for a well-formed input returns an error.
+pub trait Solver {
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Solution;
+    fn part2(input: &Self::Input) -> Solution;
+}
+
+/// Parsed input for a day whose solver type has been erased.
+pub trait Parsed {
+    fn part1(&self) -> Solution;
+    fn part2(&self) -> Solution;
+
+    fn solve(&self, part: Part) -> Solution {
+        match part {
+            Part::One => self.part1(),
+            Part::Two => self.part2(),
+        }
+    }
+}
+
+struct Generated<S: Solver> {
+    input: S::Input,
+    solver: PhantomData<S>,
+}
+
+impl<S: Solver> Parsed for Generated<S> {
+    fn part1(&self) -> Solution {
+        S::part1(&self.input)
+    }
+
+    fn part2(&self) -> Solution {
+        S::part2(&self.input)
+    }
+}
+
+/// Erases the type of input that was built without `S::parse`, such as a
+/// day's input combined with settings from the command line.
+pub fn parsed<S: Solver + 'static>(input: S::Input) -> Box<dyn Parsed> {
+    Box::new(Generated::<S> {
+        input,
+        solver: PhantomData,
+    })
+}
+
+fn generate<S: Solver + 'static>(input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+    Ok(parsed::<S>(S::parse(input)?))
+}
+
+/// A registry entry: the solver for one puzzle, callable without knowing
+/// its input type.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    generator: fn(&str) -> Result<Box<dyn Parsed>, ParseError>,
+}
+
+impl Day {
+    pub fn new<S: Solver + 'static>(year: u16, day: u8) -> Day {
+        Day {
+            year,
+            day,
+            generator: generate::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        (self.generator)(input)
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Day")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .finish()
+    }
+}
@@ -0,0 +1,196 @@
+use crate::answers::Answers;
+use crate::input::Source;
+use crate::solver::{Day, Part};
+use serde::Serialize;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Status {
+    Pass {
+        answer: String,
+    },
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    MissingAnswer {
+        actual: String,
+    },
+    Panicked {
+        message: String,
+    },
+    /// The input could not be read or parsed, the part is not solved, or
+    /// the solver found no answer for the input.
+    Error {
+        message: String,
+    },
+}
+
+impl Status {
+    /// Whether this status should make a verification run fail. A missing
+    /// answer only means the table needs updating.
+    pub fn is_failure(&self) -> bool {
+        match self {
+            Status::Pass { .. } | Status::MissingAnswer { .. } => false,
+            Status::Fail { .. } | Status::Panicked { .. } | Status::Error { .. } => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    #[serde(flatten)]
+    pub status: Status,
+}
+
+/// Runs both parts of `day` against its input and compares them with the
+/// answers table. Parts that are neither solved nor have a recorded answer
+/// are left out.
+pub fn verify_day(day: &Day, source: &Source, answers: &Answers) -> Vec<Check> {
+    let expected: Vec<Option<&str>> = Part::ALL
+        .iter()
+        .map(|&part| answers.get(day.year, day.day, part))
+        .collect();
+    let check = |part: Part, status: Status| Check {
+        year: day.year,
+        day: day.day,
+        part: part.number(),
+        status,
+    };
+    let all_failed = |status: Status| {
+        Part::ALL
+            .iter()
+            .map(|&part| check(part, status.clone()))
+            .collect()
+    };
+
+    let input = match source.read(day.year, day.day) {
+        Ok(input) => input,
+        Err(error) => {
+            return all_failed(Status::Error {
+                message: error.to_string(),
+            })
+        }
+    };
+    let parsed = match panic::catch_unwind(|| day.parse(&input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(error)) => {
+            return all_failed(Status::Error {
+                message: error.to_string(),
+            })
+        }
+        Err(payload) => {
+            return all_failed(Status::Panicked {
+                message: panic_message(payload),
+            })
+        }
+    };
+
+    Part::ALL
+        .iter()
+        .zip(expected)
+        .filter_map(|(&part, expected)| {
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+                parsed
+                    .solve(part)
+                    .map(|answer| answer.map(|answer| answer.to_string()))
+                    .map_err(|error| error.to_string())
+            }));
+            let status = match (solved, expected) {
+                (Err(payload), _) => Status::Panicked {
+                    message: panic_message(payload),
+                },
+                (Ok(Err(message)), _) => Status::Error { message },
+                (Ok(Ok(None)), None) => return None,
+                (Ok(Ok(None)), Some(_)) => Status::Error {
+                    message: String::from("not solved yet"),
+                },
+                (Ok(Ok(Some(actual))), None) => Status::MissingAnswer { actual },
+                (Ok(Ok(Some(actual))), Some(expected)) if actual == expected => {
+                    Status::Pass { answer: actual }
+                }
+                (Ok(Ok(Some(actual))), Some(expected)) => Status::Fail {
+                    expected: String::from(expected),
+                    actual,
+                },
+            };
+            Some(check(part, status))
+        })
+        .collect()
+}
+
+/// The message a panic was raised with, if it had one.
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::ANSWERS_FILE;
+    use crate::error::ParseError;
+    use crate::solver::{Solution, Solver};
+    use std::path::Path;
+
+    struct Broken;
+
+    impl Solver for Broken {
+        type Input = u32;
+
+        fn parse(input: &str) -> Result<u32, ParseError> {
+            Ok(input.len() as u32)
+        }
+
+        fn part1(input: &u32) -> Solution {
+            Ok(Some(Box::new(*input)))
+        }
+
+        fn part2(_input: &u32) -> Solution {
+            panic!("No answer found")
+        }
+    }
+
+    #[test]
+    fn statuses() {
+        let day = Day::new::<Broken>(2020, 1);
+        let answers = Answers::parse("2020 1 2 12").unwrap();
+        let file = Source::File(Path::new(ANSWERS_FILE).to_path_buf());
+        let checks = verify_day(&day, &file, &answers);
+        assert!(matches!(checks[0].status, Status::MissingAnswer { .. }));
+        assert_eq!(
+            checks[1].status,
+            Status::Panicked {
+                message: String::from("No answer found")
+            }
+        );
+
+        let answers = Answers::parse("2020 1 1 3").unwrap();
+        let missing = Source::File(Path::new("input/2020/missing.txt").to_path_buf());
+        let checks = verify_day(&day, &missing, &answers);
+        assert!(checks.iter().all(|check| check.status.is_failure()));
+    }
+
+    #[test]
+    fn real_inputs() {
+        let answers = Answers::load(Path::new(ANSWERS_FILE)).unwrap();
+        let checks: Vec<Check> = crate::registry()
+            .values()
+            .flat_map(|day| verify_day(day, &Source::Default, &answers))
+            .collect();
+        for check in &checks {
+            assert!(matches!(check.status, Status::Pass { .. }), "{:?}", check);
+        }
+        assert_eq!(checks.len(), answers.len());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::error::{lines, ParseError};
+use crate::solver::{Solution, Solver};
+
+pub fn parse_input(input: &str) -> Result<Vec<u32>, ParseError> {
+    lines(1, input)?
+        .iter()
+        .map(|line| line.parse::<u32>(line.text, "an unsigned integer"))
+        .collect()
+}
+
+/// Finds `k` entries that add up to `target`, returned in ascending order.
+/// Each entry can be used once, though equal values may appear more than
+/// once in the report.
+pub fn find_sum(entries: &[u32], k: usize, target: u64) -> Option<Vec<u32>> {
+    let mut found = None;
+    search_sorted(entries, k, target, &mut |combination| {
+        found = Some(combination.to_vec());
+        true
+    });
+    found
+}
+
+/// Finds every distinct combination of `k` entries that add up to
+/// `target`. Combinations are in ascending order, and so are the values
+/// within each one.
+pub fn find_all_sums(entries: &[u32], k: usize, target: u64) -> Vec<Vec<u32>> {
+    let mut found = Vec::new();
+    search_sorted(entries, k, target, &mut |combination| {
+        found.push(combination.to_vec());
+        false
+    });
+    found
+}
+
+fn search_sorted(entries: &[u32], k: usize, target: u64, found: &mut dyn FnMut(&[u32]) -> bool) {
+    // Checked before anything is allocated for a combination of `k`.
+    if k > entries.len() {
+        return;
+    }
+    let mut sorted = entries.to_vec();
+    sorted.sort_unstable();
+    search(&sorted, k, target, &mut Vec::with_capacity(k), found);
+}
+
+/// Fixes the smallest entry and recurses until two are left, which a
+/// two-pointer scan finds in linear time, so the search is O(n^(k-1)) for
+/// k >= 2. Returns true once `found` asks to stop.
+fn search(
+    sorted: &[u32],
+    k: usize,
+    target: u64,
+    chosen: &mut Vec<u32>,
+    found: &mut dyn FnMut(&[u32]) -> bool,
+) -> bool {
+    match k {
+        0 => target == 0 && found(chosen),
+        1 => match sorted.binary_search_by(|&entry| u64::from(entry).cmp(&target)) {
+            Ok(index) => {
+                chosen.push(sorted[index]);
+                let stop = found(chosen);
+                chosen.pop();
+                stop
+            }
+            Err(_) => false,
+        },
+        2 => {
+            let (mut low, mut high) = (0, sorted.len());
+            while low + 1 < high {
+                let sum = u64::from(sorted[low]) + u64::from(sorted[high - 1]);
+                if sum < target {
+                    low += 1;
+                } else if sum > target {
+                    high -= 1;
+                } else {
+                    chosen.extend_from_slice(&[sorted[low], sorted[high - 1]]);
+                    let stop = found(chosen);
+                    chosen.truncate(chosen.len() - 2);
+                    if stop {
+                        return true;
+                    }
+                    let (first, last) = (sorted[low], sorted[high - 1]);
+                    while low < high && sorted[low] == first {
+                        low += 1;
+                    }
+                    while high > low && sorted[high - 1] == last {
+                        high -= 1;
+                    }
+                }
+            }
+            false
+        }
+        _ => {
+            for index in 0..sorted.len().saturating_sub(k - 1) {
+                let entry = sorted[index];
+                if index > 0 && entry == sorted[index - 1] {
+                    continue;
+                }
+                // Everything after `entry` is at least as large.
+                if u64::from(entry).saturating_mul(k as u64) > target {
+                    break;
+                }
+                chosen.push(entry);
+                let stop = search(
+                    &sorted[index + 1..],
+                    k - 1,
+                    target - u64::from(entry),
+                    chosen,
+                    found,
+                );
+                chosen.pop();
+                if stop {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+fn product(entries: &[u32]) -> u64 {
+    entries.iter().map(|&entry| u64::from(entry)).product()
+}
+
+pub fn part_one(input: &[u32]) -> Option<u64> {
+    find_sum(input, 2, 2020).map(|entries| product(&entries))
+}
+
+pub fn part_two(input: &[u32]) -> Option<u64> {
+    find_sum(input, 3, 2020).map(|entries| product(&entries))
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<u32>) -> Solution {
+        let product = part_one(input).ok_or("no two entries add up to 2020")?;
+        Ok(Some(Box::new(product)))
+    }
+
+    fn part2(input: &Vec<u32>) -> Solution {
+        let product = part_two(input).ok_or("no three entries add up to 2020")?;
+        Ok(Some(Box::new(product)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"1721
+979
+366
+299
+675
+1456"#;
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(part_one(&parsed), Some(514579));
+        assert_eq!(part_two(&parsed), Some(241861950));
+        assert_eq!(part_one(&[1, 2]), None);
+    }
+
+    #[test]
+    fn k_sums() {
+        let entries = [5, 1, 4, 3, 2, 3];
+        assert_eq!(find_sum(&entries, 2, 6), Some(vec![1, 5]));
+        assert_eq!(
+            find_all_sums(&entries, 2, 6),
+            vec![vec![1, 5], vec![2, 4], vec![3, 3]]
+        );
+        assert_eq!(
+            find_all_sums(&entries, 3, 9),
+            vec![vec![1, 3, 5], vec![2, 3, 4]]
+        );
+        assert_eq!(find_all_sums(&entries, 4, 10), vec![vec![1, 2, 3, 4]]);
+        assert_eq!(find_sum(&entries, 1, 4), Some(vec![4]));
+        assert_eq!(find_sum(&entries, 0, 0), Some(vec![]));
+        assert_eq!(find_sum(&entries, 2, 100), None);
+        assert_eq!(find_sum(&entries, 7, 18), None);
+        assert_eq!(find_sum(&[1, 2], 1 << 40, 5), None);
+        assert!(find_all_sums(&[1, 2], usize::MAX, 5).is_empty());
+        assert_eq!(
+            find_sum(&[u32::MAX, u32::MAX], 2, 2 * u64::from(u32::MAX)),
+            Some(vec![u32::MAX; 2])
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("1721\n97x9\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.expected, "an unsigned integer");
+    }
+}
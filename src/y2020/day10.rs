@@ -0,0 +1,146 @@
+use crate::error::{lines, ParseError};
+use crate::solver::{Solution, Solver};
+
+fn parse_input(input: &str) -> Result<Vec<u8>, ParseError> {
+    let mut parsed = lines(10, input)?
+        .iter()
+        .map(|line| line.parse::<u8>(line.text, "a joltage rating from 0 to 255"))
+        .collect::<Result<Vec<u8>, ParseError>>()?;
+    parsed.sort_unstable();
+    Ok(parsed)
+}
+
+pub fn part_one(input: &[u8]) -> u32 {
+    // Widened so that the device, 3 jolts above the highest adapter, still
+    // fits when that adapter is rated 253 or more.
+    let mut full_input = vec![0u16];
+    full_input.extend(input.iter().map(|&rating| u16::from(rating)));
+    let device_rating = full_input.last().unwrap() + 3;
+    full_input.push(device_rating);
+    let mut differences = Vec::<u16>::new();
+
+    for chunk in full_input.windows(2) {
+        differences.push(chunk[1] - chunk[0]);
+    }
+    let ones = differences.iter().filter(|&val| *val == 1).count();
+    let threes = differences.iter().filter(|&val| *val == 3).count();
+    (ones * threes) as u32
+}
+
+/// Counts the distinct adapter chains from the outlet to the device.
+///
+/// An adapter takes input 1 to 3 jolts below its rating, so the number of
+/// ways to reach it is the sum of the ways to reach the ratings just below.
+/// The device is always 3 jolts above the last adapter and only has one
+/// way in, so it does not change the count. Returns `None` if the count
+/// does not fit in a u128.
+pub fn part_two(input: &[u8]) -> Option<u128> {
+    // Indexed by rating; the outlet is rated 0 and can be reached one way.
+    let mut ways = [0u128; 256];
+    ways[0] = 1;
+
+    for &rating in input.iter().filter(|&&rating| rating > 0) {
+        let rating = rating as usize;
+        ways[rating] = ways[rating.saturating_sub(3)..rating]
+            .iter()
+            .try_fold(0u128, |sum, &count| sum.checked_add(count))?;
+    }
+    Some(input.last().map_or(1, |&last| ways[last as usize]))
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Vec<u8>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<u8>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &Vec<u8>) -> Solution {
+        let ways = part_two(input).ok_or("arrangement count overflows u128")?;
+        Ok(Some(Box::new(ways)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let example1 = r#"16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4"#;
+
+        let parsed1 = parse_input(example1).unwrap();
+        assert_eq!(part_one(&parsed1), 35);
+        assert_eq!(part_two(&parsed1), Some(8));
+
+        let example2 = r#"28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3
+"#;
+
+        let parsed2 = parse_input(example2).unwrap();
+        assert_eq!(part_one(&parsed2), 220);
+        assert_eq!(part_two(&parsed2), Some(19208));
+    }
+
+    #[test]
+    fn large_counts() {
+        let adapters: Vec<u8> = (1..=100).collect();
+        assert_eq!(part_two(&adapters), Some(180396380815100901214157639));
+        assert_eq!(part_one(&[254]), 0);
+        assert_eq!(part_one(&[253, 254, 255]), 2);
+        let adapters: Vec<u8> = (1..=200).collect();
+        assert_eq!(part_two(&adapters), None);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("16\n10\n\n5").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert!(parse_input("16\n300").is_err());
+    }
+}
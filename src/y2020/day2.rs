@@ -0,0 +1,132 @@
+use crate::error::{lines, Line, ParseError};
+use crate::solver::{Solution, Solver};
+
+pub struct Policy {
+    subject: String,
+    min: usize,
+    max: usize,
+}
+
+pub struct PasswordWithPolicy {
+    policy: Policy,
+    password: String,
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<PasswordWithPolicy>, ParseError> {
+    lines(2, input)?
+        .iter()
+        .map(line_to_password_with_policy)
+        .collect()
+}
+
+fn line_to_password_with_policy(line: &Line) -> Result<PasswordWithPolicy, ParseError> {
+    let (policy_input, password) = line
+        .text
+        .split_once(": ")
+        .ok_or_else(|| line.error_at(line.text.len() + 1, "\": \" followed by a password"))?;
+    let (min_max, subject) = policy_input
+        .split_once(' ')
+        .ok_or_else(|| line.error_at(policy_input.len() + 1, "a space before the letter"))?;
+    let (min, max) = min_max
+        .split_once('-')
+        .ok_or_else(|| line.error(min_max, "a \"min-max\" range"))?;
+
+    let parsed_min = line.parse::<usize>(min, "a minimum count")?;
+    let parsed_max = line.parse::<usize>(max, "a maximum count")?;
+    if parsed_min == 0 {
+        return Err(line.error(min, "a minimum count of at least 1"));
+    }
+    if parsed_max < parsed_min {
+        return Err(line.error(max, "a maximum count no less than the minimum"));
+    }
+    if subject.chars().count() != 1 {
+        return Err(line.error(subject, "a single letter"));
+    }
+
+    Ok(PasswordWithPolicy {
+        password: String::from(password),
+        policy: Policy {
+            subject: String::from(subject),
+            min: parsed_min,
+            max: parsed_max,
+        },
+    })
+}
+
+fn valid_count(
+    input: &[PasswordWithPolicy],
+    validate_fun: &dyn Fn(&PasswordWithPolicy) -> bool,
+) -> usize {
+    input.iter().filter(|pw| validate_fun(pw)).count()
+}
+
+fn is_valid_password_part_one(input: &PasswordWithPolicy) -> bool {
+    let count = input.password.matches(&input.policy.subject).count();
+    count >= input.policy.min && count <= input.policy.max
+}
+
+pub fn part_one(input: &[PasswordWithPolicy]) -> usize {
+    valid_count(input, &is_valid_password_part_one)
+}
+
+fn is_valid_password_part_two(input: &PasswordWithPolicy) -> bool {
+    let chars: Vec<char> = input.password.chars().collect();
+    let subject = input.policy.subject.chars().next().unwrap();
+    // A position past the end of the password does not hold the letter.
+    let holds_subject = |position: usize| chars.get(position - 1) == Some(&subject);
+    holds_subject(input.policy.min) != holds_subject(input.policy.max)
+}
+
+pub fn part_two(input: &[PasswordWithPolicy]) -> usize {
+    valid_count(input, &is_valid_password_part_two)
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input = Vec<PasswordWithPolicy>;
+
+    fn parse(input: &str) -> Result<Vec<PasswordWithPolicy>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<PasswordWithPolicy>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &Vec<PasswordWithPolicy>) -> Solution {
+        Ok(Some(Box::new(part_two(input))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc
+"#;
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(part_one(&parsed), 2);
+        assert_eq!(part_two(&parsed), 1);
+
+        let parsed = parse_input("1-10 a: abc\n2-10 a: abc").unwrap();
+        assert_eq!(part_one(&parsed), 1);
+        assert_eq!(part_two(&parsed), 1);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("1-3 a: abcde\n1-x b: cdefg\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 2: line 2, column 3: expected a maximum count"
+        );
+
+        let error = parse_input("1-3 a abcde").err().unwrap();
+        assert_eq!(error.column, 12);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solver::{Solution, Solver};
+
+/// The map repeats to the right, so it wraps horizontally.
+pub fn parse_input(input: &str) -> Result<Grid<bool>, ParseError> {
+    let grid = Grid::parse(3, input, "'#' or '.'", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(grid.with_wrapping(true, false))
+}
+
+fn tree_count(map: &Grid<bool>, down_step: usize, right_step: usize) -> usize {
+    (1..map.height())
+        .map_while(|i| map.get(i * right_step, i * down_step))
+        .filter(|&&tree| tree)
+        .count()
+}
+
+pub fn part_one(input: &Grid<bool>) -> usize {
+    tree_count(input, 1, 3)
+}
+
+pub fn part_two(input: &Grid<bool>) -> usize {
+    let slopes = [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)];
+
+    slopes.iter().fold(1, |acc, (down_step, right_step)| {
+        tree_count(input, *down_step as usize, *right_step as usize) * acc
+    })
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Grid<bool>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Grid<bool>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &Grid<bool>) -> Solution {
+        Ok(Some(Box::new(part_two(input))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+"#;
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(part_one(&parsed), 7);
+        assert_eq!(part_two(&parsed), 336);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("..#\n.x.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+
+        let error = parse_input("..#\n.#\n").unwrap_err();
+        assert_eq!(error.expected, "a row of width 3");
+    }
+}
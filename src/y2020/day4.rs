@@ -0,0 +1,649 @@
+use crate::error::{groups, Line, ParseError};
+use crate::solver::{self, Parsed, Solution, Solver};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// The puzzle's passport policy, in the format read by [`Schema::parse`].
+pub const DEFAULT_SCHEMA: &str = include_str!("day4_rules.json");
+
+/// A passport exactly as written in the batch file: which fields it has
+/// and their unchecked values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawPassport {
+    line: usize,
+    fields: BTreeMap<String, String>,
+}
+
+impl RawPassport {
+    /// The line the passport starts on.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+}
+
+/// A passport whose fields have all passed validation, holding each
+/// present field as its checked value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Passport {
+    values: BTreeMap<String, Value>,
+}
+
+impl Passport {
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.values.get(field)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Number(u32),
+    Measurement { value: u32, unit: String },
+    Text(String),
+}
+
+/// The rules a passport must follow, loaded from a declarative JSON file
+/// so the policy can change without a recompile. Fields are checked in
+/// the order they are listed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+    fields: Vec<FieldRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldRule {
+    name: String,
+    required: bool,
+    rule: Option<Rule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Rule {
+    /// A whole number between `min` and `max` inclusive.
+    Range {
+        min: u32,
+        max: u32,
+    },
+    /// A whole number with a unit suffix, where each unit has its own range.
+    Measurement {
+        units: BTreeMap<String, Bounds>,
+    },
+    /// Text matching `pattern`, which is `description` in error messages.
+    Pattern {
+        pattern: Pattern,
+        description: String,
+    },
+    OneOf {
+        values: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Bounds {
+    min: u32,
+    max: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+struct Pattern(Regex);
+
+impl TryFrom<String> for Pattern {
+    type Error = regex::Error;
+
+    fn try_from(pattern: String) -> Result<Pattern, regex::Error> {
+        Regex::new(&pattern).map(Pattern)
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Schema {
+        Schema::parse(DEFAULT_SCHEMA).expect("the default schema is valid")
+    }
+}
+
+impl Schema {
+    pub fn parse(json: &str) -> Result<Schema, String> {
+        let schema: Schema = serde_json::from_str(json).map_err(|error| error.to_string())?;
+        for field in &schema.fields {
+            let bounds = match &field.rule {
+                Some(Rule::Range { min, max }) => vec![Bounds {
+                    min: *min,
+                    max: *max,
+                }],
+                Some(Rule::Measurement { units }) if units.is_empty() => {
+                    return Err(format!("{}: no units given", field.name))
+                }
+                Some(Rule::Measurement { units }) => units.values().copied().collect(),
+                _ => Vec::new(),
+            };
+            if bounds.iter().any(|bounds| bounds.min > bounds.max) {
+                return Err(format!("{}: min is greater than max", field.name));
+            }
+        }
+        Ok(schema)
+    }
+
+    pub fn load(path: &Path) -> Result<Schema, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        Schema::parse(&contents).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Whether every required field is present, whatever its value.
+    pub fn has_required_fields(&self, raw: &RawPassport) -> bool {
+        self.fields
+            .iter()
+            .all(|field| !field.required || raw.get(&field.name).is_some())
+    }
+
+    /// Checks every field of `raw`, reporting each one that is missing,
+    /// invalid, or not in the schema at all.
+    pub fn validate(&self, raw: &RawPassport) -> Result<Passport, Vec<FieldError>> {
+        let mut values = BTreeMap::new();
+        let mut errors = Vec::new();
+        for field in &self.fields {
+            let value = match raw.get(&field.name) {
+                Some(value) => value,
+                None if field.required => {
+                    errors.push(FieldError {
+                        field: field.name.clone(),
+                        value: None,
+                        reason: Reason::Missing,
+                    });
+                    continue;
+                }
+                None => continue,
+            };
+            let checked = match &field.rule {
+                Some(rule) => rule.check(value),
+                None => Ok(Value::Text(String::from(value))),
+            };
+            match checked {
+                Ok(checked) => {
+                    values.insert(field.name.clone(), checked);
+                }
+                Err(reason) => errors.push(FieldError {
+                    field: field.name.clone(),
+                    value: Some(String::from(value)),
+                    reason,
+                }),
+            }
+        }
+        for (key, value) in &raw.fields {
+            if !self.fields.iter().any(|field| &field.name == key) {
+                errors.push(FieldError {
+                    field: key.clone(),
+                    value: Some(value.clone()),
+                    reason: Reason::Unknown,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(Passport { values })
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Rule {
+    fn check(&self, input: &str) -> Result<Value, Reason> {
+        match self {
+            Rule::Range { min, max } => {
+                let parsed = input.parse::<u32>().map_err(|_| Reason::NotANumber)?;
+                in_range(parsed, *min, *max, "").map(Value::Number)
+            }
+            Rule::Measurement { units } => {
+                let split = input
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(input.len());
+                let (number, unit) = input.split_at(split);
+                let bounds = units.get(unit).filter(|_| !number.is_empty());
+                let bounds = bounds.ok_or_else(|| {
+                    let names: Vec<String> =
+                        units.keys().map(|unit| format!("\"{}\"", unit)).collect();
+                    Reason::BadFormat {
+                        expected: format!("a number followed by {}", names.join(" or ")),
+                    }
+                })?;
+                // Only digits are left, so parsing can only fail on overflow.
+                let value = number.parse::<u32>().unwrap_or(u32::MAX);
+                let value = in_range(value, bounds.min, bounds.max, unit)?;
+                Ok(Value::Measurement {
+                    value,
+                    unit: String::from(unit),
+                })
+            }
+            Rule::Pattern {
+                pattern,
+                description,
+            } => {
+                if pattern.0.is_match(input) {
+                    Ok(Value::Text(String::from(input)))
+                } else {
+                    Err(Reason::BadFormat {
+                        expected: description.clone(),
+                    })
+                }
+            }
+            Rule::OneOf { values } => {
+                if values.iter().any(|value| value == input) {
+                    Ok(Value::Text(String::from(input)))
+                } else {
+                    Err(Reason::NotAllowed {
+                        allowed: values.clone(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+fn in_range(value: u32, min: u32, max: u32, unit: &str) -> Result<u32, Reason> {
+    if (min..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(Reason::OutOfRange {
+            min,
+            max,
+            unit: String::from(unit),
+        })
+    }
+}
+
+/// Why a passport field failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    Missing,
+    NotANumber,
+    OutOfRange {
+        min: u32,
+        max: u32,
+        unit: String,
+    },
+    BadFormat {
+        expected: String,
+    },
+    NotAllowed {
+        allowed: Vec<String>,
+    },
+    /// The schema has no rule for this field.
+    Unknown,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::Missing => write!(f, "missing"),
+            Reason::NotANumber => write!(f, "is not a number"),
+            Reason::OutOfRange { min, max, unit } => {
+                write!(f, "is out of range {}-{}{}", min, max, unit)
+            }
+            Reason::BadFormat { expected } => write!(f, "is not {}", expected),
+            Reason::NotAllowed { allowed } => write!(f, "is not one of {}", allowed.join(", ")),
+            Reason::Unknown => write!(f, "is not a known field"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    /// The rejected value, or `None` if the field is missing.
+    pub value: Option<String>,
+    pub reason: Reason,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}: {} {}", self.field, value, self.reason),
+            None => write!(f, "{}: {}", self.field, self.reason),
+        }
+    }
+}
+
+/// Everything wrong with one passport in a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub line: usize,
+    pub errors: Vec<FieldError>,
+}
+
+impl Report {
+    pub fn new(schema: &Schema, raw: &RawPassport) -> Report {
+        Report {
+            line: raw.line,
+            errors: schema.validate(raw).err().unwrap_or_default(),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "passport on line {}: ", self.line)?;
+        if self.is_valid() {
+            return write!(f, "valid");
+        }
+        let errors: Vec<String> = self.errors.iter().map(|e| e.to_string()).collect();
+        write!(f, "{}", errors.join("; "))
+    }
+}
+
+/// Counts over the reports for a whole batch: how many passports are
+/// valid, and how often each field is missing or invalid.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub total: usize,
+    pub valid: usize,
+    pub missing: BTreeMap<String, usize>,
+    pub invalid: BTreeMap<String, usize>,
+}
+
+impl Summary {
+    pub fn of(reports: &[Report]) -> Summary {
+        let mut summary = Summary {
+            total: reports.len(),
+            ..Summary::default()
+        };
+        for report in reports {
+            if report.is_valid() {
+                summary.valid += 1;
+            }
+            for error in &report.errors {
+                let counts = match error.reason {
+                    Reason::Missing => &mut summary.missing,
+                    _ => &mut summary.invalid,
+                };
+                *counts.entry(error.field.clone()).or_insert(0) += 1;
+            }
+        }
+        summary
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} passports, {} valid", self.total, self.valid)?;
+        let fields: BTreeSet<&String> = self.missing.keys().chain(self.invalid.keys()).collect();
+        for field in fields {
+            let missing = self.missing.get(field).copied().unwrap_or(0);
+            let invalid = self.invalid.get(field).copied().unwrap_or(0);
+            write!(f, "\n  {}: {} missing, {} invalid", field, missing, invalid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Validates a whole batch against `schema`, one report per passport.
+pub fn audit(schema: &Schema, passports: &[RawPassport]) -> Vec<Report> {
+    passports
+        .iter()
+        .map(|passport| Report::new(schema, passport))
+        .collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<RawPassport>, ParseError> {
+    groups(4, input)?
+        .iter()
+        .map(|group| parse_group(group))
+        .collect()
+}
+
+fn parse_group(group: &[Line]) -> Result<RawPassport, ParseError> {
+    let mut passport = RawPassport {
+        line: group[0].number,
+        fields: BTreeMap::new(),
+    };
+    for line in group {
+        for kv in line.text.split_whitespace() {
+            let (key, value) = kv
+                .split_once(':')
+                .ok_or_else(|| line.error(kv, "a \"key:value\" field"))?;
+            if passport
+                .fields
+                .insert(String::from(key), String::from(value))
+                .is_some()
+            {
+                return Err(line.error(kv, format!("a single \"{}\" field", key)));
+            }
+        }
+    }
+
+    Ok(passport)
+}
+
+/// The default schema, parsed the first time it is needed.
+fn default_schema() -> Arc<Schema> {
+    static SCHEMA: OnceLock<Arc<Schema>> = OnceLock::new();
+    SCHEMA.get_or_init(|| Arc::new(Schema::default())).clone()
+}
+
+/// A batch of passports and the schema the solver checks them against.
+pub struct Batch {
+    schema: Arc<Schema>,
+    passports: Vec<RawPassport>,
+}
+
+/// Parses a batch for the solver to check against `schema` instead of the
+/// default one, so a policy loaded with [`Schema::load`] can be solved
+/// without a recompile.
+pub fn parse_with_schema(input: &str, schema: Schema) -> Result<Box<dyn Parsed>, ParseError> {
+    Ok(solver::parsed::<Day4>(Batch {
+        schema: Arc::new(schema),
+        passports: parse_input(input)?,
+    }))
+}
+
+fn part_one(schema: &Schema, input: &[RawPassport]) -> u32 {
+    input
+        .iter()
+        .filter(|passport| schema.has_required_fields(passport))
+        .count() as u32
+}
+
+fn part_two(schema: &Schema, input: &[RawPassport]) -> u32 {
+    input
+        .iter()
+        .filter(|passport| schema.validate(passport).is_ok())
+        .count() as u32
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input = Batch;
+
+    fn parse(input: &str) -> Result<Batch, ParseError> {
+        Ok(Batch {
+            schema: default_schema(),
+            passports: parse_input(input)?,
+        })
+    }
+
+    fn part1(input: &Batch) -> Solution {
+        Ok(Some(Box::new(part_one(&input.schema, &input.passports))))
+    }
+
+    fn part2(input: &Batch) -> Solution {
+        Ok(Some(Box::new(part_two(&input.schema, &input.passports))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in"#;
+        let parsed = parse_input(input).unwrap();
+        let schema = default_schema();
+        assert_eq!(part_one(&schema, &parsed), 2);
+        assert_eq!(part_two(&schema, &parsed), 2);
+        assert_eq!(parsed[1].get("hgt"), None);
+        assert_eq!(
+            Schema::default().validate(&parsed[0]).unwrap().get("hgt"),
+            Some(&Value::Measurement {
+                value: 183,
+                unit: String::from("cm")
+            })
+        );
+
+        let invalid = r#"eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007"#;
+        let parsed = parse_input(invalid).unwrap();
+        assert_eq!(part_one(&schema, &parsed), 4);
+        assert_eq!(part_two(&schema, &parsed), 0);
+
+        let valid = r#"pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719"#;
+        let parsed = parse_input(valid).unwrap();
+        assert_eq!(part_two(&schema, &parsed), 4);
+    }
+
+    #[test]
+    fn diagnostics() {
+        let input = "byr:1937 iyr:2017 eyr:2020 hgt:190in hcl:123abc ecl:xyz\n\n\
+                     byr:1937 iyr:2017 eyr:2020 hgt:183cm hcl:#fffffd ecl:gry pid:860033327";
+        let reports = audit(&Schema::default(), &parse_input(input).unwrap());
+        let messages: Vec<String> = reports[0].errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "hgt: 190in is out of range 59-76in",
+                "hcl: 123abc is not # followed by 6 hex digits",
+                "ecl: xyz is not one of amb, blu, brn, gry, grn, hzl, oth",
+                "pid: missing",
+            ]
+        );
+        assert_eq!(reports[0].errors[3].reason, Reason::Missing);
+        assert!(reports[1].is_valid());
+        assert_eq!(reports[1].to_string(), "passport on line 3: valid");
+
+        let summary = Summary::of(&reports);
+        assert_eq!((summary.total, summary.valid), (2, 1));
+        assert_eq!(summary.missing.get("pid"), Some(&1));
+        assert_eq!(summary.invalid.get("hgt"), Some(&1));
+        assert_eq!(
+            summary.to_string(),
+            "2 passports, 1 valid\n  ecl: 0 missing, 1 invalid\n  \
+             hcl: 0 missing, 1 invalid\n  hgt: 0 missing, 1 invalid\n  \
+             pid: 1 missing, 0 invalid"
+        );
+    }
+
+    #[test]
+    fn custom_schema() {
+        let schema = Schema::parse(
+            r#"{"fields": [
+                {"name": "hgt", "required": true, "rule": {
+                    "type": "measurement", "units": {"in": {"min": 59, "max": 80}}}},
+                {"name": "pid", "required": false}
+            ]}"#,
+        )
+        .unwrap();
+        let passports = parse_input("hgt:78in\n\nhgt:190cm pid:1\n\npid:1").unwrap();
+        let reports = audit(&schema, &passports);
+        assert!(reports[0].is_valid());
+        assert_eq!(
+            reports[1].to_string(),
+            "passport on line 3: hgt: 190cm is not a number followed by \"in\""
+        );
+        assert_eq!(reports[2].to_string(), "passport on line 5: hgt: missing");
+        assert!(!schema.has_required_fields(&passports[2]));
+
+        let passports = parse_input("hgt:78in pid:1 eye:blue").unwrap();
+        assert!(schema.has_required_fields(&passports[0]));
+        assert_eq!(
+            audit(&schema, &passports)[0].to_string(),
+            "passport on line 1: eye: blue is not a known field"
+        );
+
+        let error = Schema::parse(
+            r#"{"fields": [{"name": "pid", "required": true,
+                "rule": {"type": "pattern", "pattern": "(", "description": "x"}}]}"#,
+        )
+        .unwrap_err();
+        assert!(error.contains("regex parse error"), "{}", error);
+        let error = Schema::parse(
+            r#"{"fields": [{"name": "byr", "required": true,
+                "rule": {"type": "range", "min": 2002, "max": 1920}}]}"#,
+        )
+        .unwrap_err();
+        assert_eq!(error, "byr: min is greater than max");
+
+        let schema = Schema::parse(r#"{"fields": [{"name": "pid", "required": true}]}"#).unwrap();
+        let parsed = parse_with_schema("pid:1\n\ncid:2\n\npid:3 cid:4", schema).unwrap();
+        assert_eq!(parsed.part1().unwrap().unwrap().to_string(), "2");
+        assert_eq!(parsed.part2().unwrap().unwrap().to_string(), "1");
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("ecl:gry pid:860033327\n\nhcl:#ae17e1 iyr2013")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 4: line 3, column 13: expected a \"key:value\" field"
+        );
+        let error = parse_input("ecl:gry pid:860033327 ecl:blu").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 4: line 1, column 23: expected a single \"ecl\" field"
+        );
+    }
+}
@@ -0,0 +1,466 @@
+use crate::error::{lines, ParseError};
+use crate::grid::Grid;
+use crate::solver::{Solution, Solver};
+use std::error::Error;
+use std::fmt;
+
+/// How many characters of a boarding pass pick the row and the column.
+/// Each character halves the remaining range, so a plane has
+/// `2^row_bits` rows of `2^column_bits` seats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Layout {
+    row_bits: u32,
+    column_bits: u32,
+}
+
+impl Default for Layout {
+    /// The puzzle's plane: 128 rows of 8 seats.
+    fn default() -> Layout {
+        Layout::new(7, 3).unwrap()
+    }
+}
+
+impl Layout {
+    /// Fails if the layout has more than 2^31 seats, which would not fit
+    /// in a seat id.
+    pub fn new(row_bits: u32, column_bits: u32) -> Result<Layout, CodecError> {
+        if row_bits
+            .checked_add(column_bits)
+            .is_none_or(|bits| bits >= 32)
+        {
+            return Err(CodecError::TooManySeats {
+                row_bits,
+                column_bits,
+            });
+        }
+        Ok(Layout {
+            row_bits,
+            column_bits,
+        })
+    }
+
+    pub fn rows(self) -> u32 {
+        1 << self.row_bits
+    }
+
+    pub fn columns(self) -> u32 {
+        1 << self.column_bits
+    }
+
+    /// The length of an encoded boarding pass.
+    pub fn len(self) -> usize {
+        (self.row_bits + self.column_bits) as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Describes the characters allowed at a 0-based position.
+    fn expected_at(self, index: usize) -> &'static str {
+        if index < self.row_bits as usize {
+            "'F' or 'B'"
+        } else if index < self.len() {
+            "'L' or 'R'"
+        } else {
+            "the end of the line"
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// The character at `position`, counting from 1, is not allowed there.
+    Character {
+        position: usize,
+        found: char,
+        expected: &'static str,
+    },
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    OutOfRange {
+        row: u32,
+        column: u32,
+    },
+    /// A layout whose seat ids would not fit in a `u32`.
+    TooManySeats {
+        row_bits: u32,
+        column_bits: u32,
+    },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodecError::Character {
+                position,
+                found,
+                expected,
+            } => write!(
+                f,
+                "position {}: expected {}, found {:?}",
+                position, expected, found
+            ),
+            CodecError::Length { expected, found } => {
+                write!(f, "expected {} characters, found {}", expected, found)
+            }
+            CodecError::OutOfRange { row, column } => {
+                write!(f, "row {}, column {} is not on the plane", row, column)
+            }
+            CodecError::TooManySeats {
+                row_bits,
+                column_bits,
+            } => write!(
+                f,
+                "{} row bits and {} column bits are too many seats for a u32 id",
+                row_bits, column_bits
+            ),
+        }
+    }
+}
+
+impl Error for CodecError {}
+
+/// A seat, which converts to and from the binary space partitioning code
+/// printed on a boarding pass: `F`/`B` pick the front or back half of the
+/// rows and `L`/`R` the left or right half of the columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoardingPass {
+    row: u32,
+    column: u32,
+    layout: Layout,
+}
+
+impl BoardingPass {
+    pub fn new(layout: Layout, row: u32, column: u32) -> Result<BoardingPass, CodecError> {
+        if row >= layout.rows() || column >= layout.columns() {
+            return Err(CodecError::OutOfRange { row, column });
+        }
+        Ok(BoardingPass {
+            row,
+            column,
+            layout,
+        })
+    }
+
+    pub fn decode(code: &str, layout: Layout) -> Result<BoardingPass, CodecError> {
+        let found = code.chars().count();
+        let mut bits = 0;
+        for (index, c) in code.chars().take(layout.len()).enumerate() {
+            let bit = match (index < layout.row_bits as usize, c) {
+                (true, 'F') | (false, 'L') => 0,
+                (true, 'B') | (false, 'R') => 1,
+                _ => {
+                    return Err(CodecError::Character {
+                        position: index + 1,
+                        found: c,
+                        expected: layout.expected_at(index),
+                    })
+                }
+            };
+            bits = bits << 1 | bit;
+        }
+
+        if found != layout.len() {
+            return Err(CodecError::Length {
+                expected: layout.len(),
+                found,
+            });
+        }
+        Ok(BoardingPass {
+            row: bits >> layout.column_bits,
+            column: bits & (layout.columns() - 1),
+            layout,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        let mut code = String::with_capacity(self.layout.len());
+        push_bits(&mut code, self.row, self.layout.row_bits, ('F', 'B'));
+        push_bits(&mut code, self.column, self.layout.column_bits, ('L', 'R'));
+        code
+    }
+
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The seat id, `row * columns + column`.
+    pub fn id(&self) -> u32 {
+        self.row << self.layout.column_bits | self.column
+    }
+}
+
+/// Writes the low `width` bits of `value`, most significant first.
+fn push_bits(code: &mut String, value: u32, width: u32, (zero, one): (char, char)) {
+    for bit in (0..width).rev() {
+        code.push(if value >> bit & 1 == 1 { one } else { zero });
+    }
+}
+
+impl fmt::Display for BoardingPass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+/// How many boarding passes were issued for each seat of a plane, with
+/// rows running from the front and columns from the left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatMap {
+    layout: Layout,
+    seats: Grid<usize>,
+}
+
+impl SeatMap {
+    /// The most seats a map can cover. Every seat gets a cell, so larger
+    /// planes would need gigabytes.
+    pub const MAX_SEATS: u64 = 1 << 20;
+
+    /// Returns `None` if the layout has more than `MAX_SEATS` seats. Panics
+    /// if a pass was encoded for a different layout.
+    pub fn new(layout: Layout, passes: &[BoardingPass]) -> Option<SeatMap> {
+        if u64::from(layout.rows()) * u64::from(layout.columns()) > SeatMap::MAX_SEATS {
+            return None;
+        }
+        let (width, height) = (layout.columns() as usize, layout.rows() as usize);
+        let mut seats = Grid::new(width, height, vec![0; width * height]);
+        for pass in passes {
+            assert_eq!(pass.layout(), layout, "boarding pass for another plane");
+            *seats
+                .get_mut(pass.column() as usize, pass.row() as usize)
+                .unwrap() += 1;
+        }
+        Some(SeatMap { layout, seats })
+    }
+
+    fn pass(&self, (column, row): (usize, usize)) -> BoardingPass {
+        BoardingPass {
+            row: row as u32,
+            column: column as u32,
+            layout: self.layout,
+        }
+    }
+
+    pub fn is_occupied(&self, row: u32, column: u32) -> bool {
+        self.seats
+            .get(column as usize, row as usize)
+            .is_some_and(|&count| count > 0)
+    }
+
+    /// Empty seats in every row from the first to the last one with an
+    /// occupied seat. Rows in front of and behind those are missing from
+    /// the plane, so their seats do not count.
+    pub fn empty_seats(&self) -> Vec<BoardingPass> {
+        let mut occupied_rows = self
+            .seats
+            .cells()
+            .filter(|(_, &count)| count > 0)
+            .map(|((_, row), _)| row);
+        let first = match occupied_rows.next() {
+            Some(first) => first,
+            None => return Vec::new(),
+        };
+        let last = occupied_rows.last().unwrap_or(first);
+        self.seats
+            .cells()
+            .filter(|&((_, row), &count)| count == 0 && (first..=last).contains(&row))
+            .map(|(position, _)| self.pass(position))
+            .collect()
+    }
+
+    /// Seats that more than one pass was issued for, and how many passes
+    /// each one has.
+    pub fn duplicates(&self) -> Vec<(BoardingPass, usize)> {
+        self.seats
+            .cells()
+            .filter(|(_, &count)| count > 1)
+            .map(|(position, &count)| (self.pass(position), count))
+            .collect()
+    }
+
+    /// Draws one line per row: `#` for a taken seat, `!` for a seat with
+    /// duplicate passes and `.` for an empty one.
+    pub fn render(&self) -> String {
+        self.seats.render(|&count| match count {
+            0 => '.',
+            1 => '#',
+            _ => '!',
+        })
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<BoardingPass>, ParseError> {
+    let layout = Layout::default();
+    lines(5, input)?
+        .iter()
+        .map(|line| {
+            BoardingPass::decode(line.text, layout).map_err(|error| match error {
+                CodecError::Character {
+                    position, expected, ..
+                } => line.error_at(position, expected),
+                CodecError::Length { found, .. } => {
+                    let index = found.min(layout.len());
+                    line.error_at(index + 1, layout.expected_at(index))
+                }
+                CodecError::OutOfRange { .. } | CodecError::TooManySeats { .. } => {
+                    unreachable!("decoding only checks characters and length")
+                }
+            })
+        })
+        .collect()
+}
+
+fn part_one(input: &[BoardingPass]) -> u32 {
+    input.iter().map(BoardingPass::id).max().unwrap()
+}
+
+/// The empty seat whose neighbours by id are both taken.
+fn part_two(input: &[BoardingPass]) -> Option<u32> {
+    let layout = Layout::default();
+    let map = SeatMap::new(layout, input)?;
+    let occupied = |id: u32| map.is_occupied(id / layout.columns(), id % layout.columns());
+    map.empty_seats()
+        .iter()
+        .map(BoardingPass::id)
+        .find(|&id| id > 0 && occupied(id - 1) && occupied(id + 1))
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input = Vec<BoardingPass>;
+
+    fn parse(input: &str) -> Result<Vec<BoardingPass>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<BoardingPass>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &Vec<BoardingPass>) -> Solution {
+        let seat = part_two(input).ok_or("no empty seat between two taken ones")?;
+        Ok(Some(Box::new(seat)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let one = parse_input("FBFBBFFRLR").unwrap();
+        assert_eq!(part_one(&one), 357);
+
+        let two = parse_input("BFFFBBFRRR").unwrap();
+        assert_eq!(part_one(&two), 567);
+
+        let three = parse_input("FFFBBBFRRR").unwrap();
+        assert_eq!(part_one(&three), 119);
+
+        let four = parse_input("BBFFBBFRLL").unwrap();
+        assert_eq!(part_one(&four), 820);
+    }
+
+    #[test]
+    fn seat_map() {
+        let layout = Layout::new(2, 2).unwrap();
+        let passes: Vec<BoardingPass> = [(1, 1), (1, 2), (2, 0), (2, 0), (2, 2)]
+            .iter()
+            .map(|&(row, column)| BoardingPass::new(layout, row, column).unwrap())
+            .collect();
+        let map = SeatMap::new(layout, &passes).unwrap();
+        assert!(map.is_occupied(1, 2));
+        assert!(!map.is_occupied(0, 0) && !map.is_occupied(9, 9));
+        assert_eq!(map.render(), "....\n.##.\n!.#.\n....\n");
+
+        let empty: Vec<u32> = map.empty_seats().iter().map(BoardingPass::id).collect();
+        assert_eq!(empty, vec![4, 7, 9, 11]);
+        assert_eq!(map.duplicates(), vec![(passes[2], 2)]);
+        assert_eq!(SeatMap::new(layout, &[]).unwrap().empty_seats(), vec![]);
+        assert_eq!(SeatMap::new(Layout::new(11, 10).unwrap(), &[]), None);
+
+        let passes = parse_input("FBFBBFFRLR\nFBFBBFFRRR").unwrap();
+        assert_eq!(part_two(&passes), Some(358));
+        assert_eq!(part_two(&passes[..1]), None);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("FBFBBFFRLR\nFBFXBFFRLR").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(error.expected, "'F' or 'B'");
+
+        let error = parse_input("FBFBBFFRL").err().unwrap();
+        assert_eq!((error.column, error.expected.as_str()), (10, "'L' or 'R'"));
+
+        let error = parse_input("FBFBBFFRLRL").err().unwrap();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (11, "the end of the line")
+        );
+    }
+
+    #[test]
+    fn codec() {
+        let layout = Layout::default();
+        let pass = BoardingPass::decode("FBFBBFFRLR", layout).unwrap();
+        assert_eq!((pass.row(), pass.column(), pass.id()), (44, 5, 357));
+        assert_eq!(pass.encode(), "FBFBBFFRLR");
+        assert_eq!(BoardingPass::new(layout, 44, 5), Ok(pass));
+        assert_eq!(
+            BoardingPass::new(layout, 128, 0),
+            Err(CodecError::OutOfRange {
+                row: 128,
+                column: 0
+            })
+        );
+
+        let small = Layout::new(2, 1).unwrap();
+        assert_eq!((small.rows(), small.columns(), small.len()), (4, 2, 3));
+        let pass = BoardingPass::new(small, 2, 1).unwrap();
+        assert_eq!((pass.encode(), pass.id()), (String::from("BFR"), 5));
+        assert_eq!(BoardingPass::decode("BFR", small), Ok(pass));
+        assert_eq!(Layout::new(16, 15).unwrap().len(), 31);
+        assert_eq!(
+            Layout::new(u32::MAX, 1),
+            Err(CodecError::TooManySeats {
+                row_bits: u32::MAX,
+                column_bits: 1
+            })
+        );
+        assert!(Layout::new(16, 16).is_err());
+
+        assert_eq!(
+            BoardingPass::decode("BFL", layout),
+            Err(CodecError::Character {
+                position: 3,
+                found: 'L',
+                expected: "'F' or 'B'"
+            })
+        );
+        assert_eq!(
+            BoardingPass::decode("BFRL", small),
+            Err(CodecError::Length {
+                expected: 3,
+                found: 4
+            })
+        );
+        assert_eq!(
+            BoardingPass::decode("BF", small).unwrap_err().to_string(),
+            "expected 3 characters, found 2"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use crate::error::{groups, ParseError};
+use crate::solver::{Solution, Solver};
+use std::fmt;
+
+/// The questions, from `a` to `z`, that someone answered "yes" to, stored
+/// as one bit per question.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AnswerSet(u32);
+
+impl AnswerSet {
+    pub const ALL: AnswerSet = AnswerSet((1 << 26) - 1);
+
+    fn bit(question: char) -> u32 {
+        assert!(
+            question.is_ascii_lowercase(),
+            "not a question: {:?}",
+            question
+        );
+        1 << (question as u8 - b'a')
+    }
+
+    /// Panics if `question` is not between `a` and `z`.
+    pub fn insert(&mut self, question: char) {
+        self.0 |= AnswerSet::bit(question);
+    }
+
+    pub fn contains(self, question: char) -> bool {
+        question.is_ascii_lowercase() && self.0 & AnswerSet::bit(question) != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn union(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: AnswerSet) -> AnswerSet {
+        AnswerSet(self.0 & other.0)
+    }
+
+    pub fn questions(self) -> impl Iterator<Item = char> {
+        (b'a'..=b'z')
+            .map(char::from)
+            .filter(move |&question| self.contains(question))
+    }
+}
+
+impl fmt::Debug for AnswerSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{}}}", self.questions().collect::<String>())
+    }
+}
+
+/// The answers of everyone in one group, one set per member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    members: Vec<AnswerSet>,
+}
+
+impl Group {
+    pub fn new(members: Vec<AnswerSet>) -> Group {
+        Group { members }
+    }
+
+    pub fn members(&self) -> &[AnswerSet] {
+        &self.members
+    }
+
+    /// How many members answered each question, indexed from `a`.
+    pub fn counts(&self) -> [usize; 26] {
+        let mut counts = [0; 26];
+        for member in &self.members {
+            for (index, count) in counts.iter_mut().enumerate() {
+                *count += (member.0 >> index & 1) as usize;
+            }
+        }
+        counts
+    }
+
+    /// The questions that at least `k` members answered. A question nobody
+    /// answered is never included, even for a `k` of 0, so an empty group
+    /// has no answers at all.
+    pub fn answered_by(&self, k: usize) -> AnswerSet {
+        match k {
+            0 | 1 => self
+                .members
+                .iter()
+                .fold(AnswerSet::default(), |set, &member| set.union(member)),
+            k if k == self.members.len() => self
+                .members
+                .iter()
+                .fold(AnswerSet::ALL, |set, &member| set.intersection(member)),
+            k if k > self.members.len() => AnswerSet::default(),
+            k => {
+                let counts = self.counts();
+                let bits = (0..26)
+                    .filter(|&index| counts[index] >= k)
+                    .fold(0, |bits, index| bits | 1 << index);
+                AnswerSet(bits)
+            }
+        }
+    }
+
+    /// The questions that everyone in the group answered.
+    pub fn answered_by_all(&self) -> AnswerSet {
+        self.answered_by(self.members.len())
+    }
+}
+
+/// For each question, indexed from `a`, how many groups had at least `k`
+/// members answer it.
+pub fn question_counts(groups: &[Group], k: usize) -> [usize; 26] {
+    let mut counts = [0; 26];
+    for group in groups {
+        let answered = group.answered_by(k);
+        for (index, count) in counts.iter_mut().enumerate() {
+            *count += (answered.0 >> index & 1) as usize;
+        }
+    }
+    counts
+}
+
+fn parse_input(input: &str) -> Result<Vec<Group>, ParseError> {
+    groups(6, input)?
+        .iter()
+        .map(|group| {
+            let mut members = Vec::with_capacity(group.len());
+            for line in group {
+                let mut answers = AnswerSet::default();
+                for (index, question) in line.text.chars().enumerate() {
+                    if !question.is_ascii_lowercase() {
+                        return Err(line.error_at(index + 1, "a question from 'a' to 'z'"));
+                    }
+                    answers.insert(question);
+                }
+                members.push(answers);
+            }
+            Ok(Group::new(members))
+        })
+        .collect()
+}
+
+fn part_one(input: &[Group]) -> u32 {
+    input
+        .iter()
+        .map(|group| group.answered_by(1).len() as u32)
+        .sum()
+}
+
+fn part_two(input: &[Group]) -> u32 {
+    input
+        .iter()
+        .map(|group| group.answered_by_all().len() as u32)
+        .sum()
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input = Vec<Group>;
+
+    fn parse(input: &str) -> Result<Vec<Group>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<Group>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &Vec<Group>) -> Solution {
+        Ok(Some(Box::new(part_two(input))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b"#;
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(part_one(&parsed), 11);
+        assert_eq!(part_two(&parsed), 6);
+
+        let counts = question_counts(&parsed, 1);
+        assert_eq!(&counts[..4], &[4, 4, 3, 0]);
+        assert_eq!(counts.iter().sum::<usize>(), 11);
+    }
+
+    #[test]
+    fn quorum() {
+        let group = &parse_input("abc\nab\nad\nz").unwrap()[0];
+        assert_eq!(group.counts()[..4], [3, 2, 1, 1]);
+        assert_eq!(format!("{:?}", group.answered_by(1)), "{abcdz}");
+        assert_eq!(format!("{:?}", group.answered_by(2)), "{ab}");
+        assert_eq!(format!("{:?}", group.answered_by(3)), "{a}");
+        assert!(group.answered_by_all().is_empty());
+        assert!(group.answered_by(5).is_empty());
+        assert_eq!(group.answered_by(0), group.answered_by(1));
+        let empty = Group::new(Vec::new());
+        assert!(empty.answered_by_all().is_empty());
+        assert!(empty.answered_by(0).is_empty());
+        assert!(group.members()[0].contains('c') && !group.members()[0].contains('?'));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("abc\n\na\nB\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+    }
+}
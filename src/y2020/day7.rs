@@ -0,0 +1,851 @@
+use crate::error::{lines, Line, ParseError};
+use crate::graph::{CycleError, Graph, NodeId};
+use crate::solver::{Solution, Solver};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    description: String,
+    children: Vec<(u32, String)>,
+}
+
+impl fmt::Display for Bag {
+    /// Writes the bag's rule in the puzzle's canonical form.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bags contain ", self.description)?;
+        if self.children.is_empty() {
+            return write!(f, "no other bags.");
+        }
+        for (index, (count, child)) in self.children.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {} {}", count, child, noun(*count))?;
+        }
+        write!(f, ".")
+    }
+}
+
+fn noun(count: u32) -> &'static str {
+    if count == 1 {
+        "bag"
+    } else {
+        "bags"
+    }
+}
+
+/// Writes a rule set back out in canonical form, one rule per line sorted
+/// by colour. Parsing the output gives back the same rules.
+pub fn serialize(rules: &HashMap<String, Bag>) -> String {
+    let mut bags: Vec<&Bag> = rules.values().collect();
+    bags.sort_unstable_by_key(|bag| &bag.description);
+    bags.iter().map(|bag| format!("{}\n", bag)).collect()
+}
+
+fn parse_input(input: &str) -> Result<HashMap<String, Bag>, ParseError> {
+    let mut dict = HashMap::<String, Bag>::new();
+    for row in lines(7, input)? {
+        let bag = RuleParser::new(&row).rule()?;
+        if dict.contains_key(&bag.description) {
+            return Err(row.error_at(1, format!("a single rule for {}", bag.description)));
+        }
+        dict.insert(bag.description.clone(), bag);
+    }
+    Ok(dict)
+}
+
+/// Reads one rule line following the grammar
+///
+/// ```text
+/// rule     = colour " bags contain " contents "."
+/// contents = "no other bags" | item { ", " item }
+/// item     = count " " colour (" bag" | " bags")
+/// colour   = word { " " word }
+/// ```
+///
+/// where a word is lowercase letters, a count is a positive number, and
+/// `bag` is used for a count of one and `bags` otherwise.
+struct RuleParser<'a> {
+    line: &'a Line<'a>,
+    rest: &'a str,
+}
+
+impl<'a> RuleParser<'a> {
+    fn new(line: &'a Line<'a>) -> RuleParser<'a> {
+        RuleParser {
+            line,
+            rest: line.text,
+        }
+    }
+
+    fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.line.error(self.rest, expected)
+    }
+
+    fn take(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    /// Consumes `literal` if the input continues with it.
+    fn accept(&mut self, literal: &str) -> bool {
+        if self.rest.starts_with(literal) {
+            self.take(literal.len());
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.accept(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("{:?}", literal)))
+        }
+    }
+
+    fn word(&mut self) -> &'a str {
+        let len = self
+            .rest
+            .find(|c: char| !c.is_ascii_lowercase())
+            .unwrap_or(self.rest.len());
+        self.take(len)
+    }
+
+    /// Reads a colour followed by `noun`, which must be "bag" or "bags".
+    fn bag(&mut self, noun: &str) -> Result<String, ParseError> {
+        let mut words = Vec::new();
+        loop {
+            let start = self.rest;
+            let word = self.word();
+            if word.is_empty() {
+                let expected = if words.is_empty() {
+                    String::from("a colour")
+                } else {
+                    format!("{:?} or another word of the colour", noun)
+                };
+                return Err(self.error(expected));
+            }
+            if word == "bag" || word == "bags" {
+                if words.is_empty() {
+                    return Err(self.line.error(start, "a colour"));
+                } else if word != noun {
+                    return Err(self.line.error(start, format!("{:?}", noun)));
+                }
+                return Ok(words.join(" "));
+            }
+            words.push(word);
+            if !self.accept(" ") {
+                return Err(self.error(format!("\" {}\"", noun)));
+            }
+        }
+    }
+
+    fn count(&mut self) -> Result<u32, ParseError> {
+        let len = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        let digits = &self.rest[..len];
+        match digits.parse::<u32>() {
+            Ok(count) if count > 0 => {
+                self.take(len);
+                Ok(count)
+            }
+            _ => Err(self.error("a bag count")),
+        }
+    }
+
+    fn rule(mut self) -> Result<Bag, ParseError> {
+        let description = self.bag("bags")?;
+        self.expect(" contain ")?;
+        let mut children = Vec::new();
+        if !self.accept("no other bags") {
+            loop {
+                let count = self.count()?;
+                self.expect(" ")?;
+                children.push((count, self.bag(noun(count))?));
+                if !self.accept(", ") {
+                    break;
+                }
+            }
+        }
+        self.expect(".")?;
+        if !self.rest.is_empty() {
+            return Err(self.error("the end of the line"));
+        }
+        Ok(Bag {
+            description,
+            children,
+        })
+    }
+}
+
+const SHINY_GOLD: &str = "shiny gold";
+
+/// Builds the containment graph: an edge from every bag to each bag it
+/// must directly hold, weighted by how many of them it holds.
+pub fn rule_graph(input: &HashMap<String, Bag>) -> Graph<String, u32> {
+    let mut graph = Graph::new();
+    for bag in input.values() {
+        graph.intern(bag.description.clone());
+        for (count, child) in &bag.children {
+            graph.add_edge(bag.description.clone(), child.clone(), *count);
+        }
+    }
+    graph
+}
+
+/// Answers questions about any colour in a rule set. Containers and totals
+/// are cached per colour and built from the cached results of the bags
+/// directly holding or held by it, so asking about every colour visits
+/// each rule once.
+pub struct BagQuery {
+    graph: Graph<String, u32>,
+    /// One bit per bag for each colour whose containers are known.
+    containers: Vec<Option<Vec<u64>>>,
+    totals: Vec<Option<u128>>,
+}
+
+/// Adds `bag` to a set of bags stored one bit per bag.
+fn insert(bags: &mut [u64], bag: NodeId) {
+    bags[bag.index() / 64] |= 1 << (bag.index() % 64);
+}
+
+/// Why the total contents of a bag could not be counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountError {
+    UnknownColour(String),
+    /// The bag ends up inside itself, so it would hold infinitely many.
+    Cycle(CycleError<String>),
+    /// The total for `colour` does not fit in a `u128`.
+    Overflow {
+        colour: String,
+    },
+}
+
+impl fmt::Display for CountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CountError::UnknownColour(colour) => write!(f, "no rule for {} bags", colour),
+            CountError::Cycle(cycle) => write!(f, "{}", cycle),
+            CountError::Overflow { colour } => {
+                write!(f, "{} bags hold too many bags to count", colour)
+            }
+        }
+    }
+}
+
+impl Error for CountError {}
+
+impl BagQuery {
+    pub fn new(rules: &HashMap<String, Bag>) -> BagQuery {
+        let graph = rule_graph(rules);
+        BagQuery {
+            containers: vec![None; graph.len()],
+            totals: vec![None; graph.len()],
+            graph,
+        }
+    }
+
+    fn names(&self, edges: &[(NodeId, u32)]) -> Vec<(u32, &str)> {
+        edges
+            .iter()
+            .map(|&(bag, count)| (count, self.graph.node(bag).as_str()))
+            .collect()
+    }
+
+    /// The bags that directly hold `colour`, and how many of it each one
+    /// holds, sorted by colour.
+    pub fn parents(&self, colour: &str) -> Option<Vec<(u32, &str)>> {
+        let bag = self.graph.id(colour)?;
+        let mut parents = self.names(self.graph.predecessors(bag));
+        parents.sort_unstable_by_key(|&(_, parent)| parent);
+        Some(parents)
+    }
+
+    /// The bags `colour` directly holds, in the order its rule lists them.
+    pub fn children(&self, colour: &str) -> Option<Vec<(u32, &str)>> {
+        let bag = self.graph.id(colour)?;
+        Some(self.names(self.graph.successors(bag)))
+    }
+
+    /// Every bag that can eventually hold `colour`, sorted by colour.
+    pub fn containers(&mut self, colour: &str) -> Option<Vec<&str>> {
+        let bag = self.graph.id(colour)?;
+        if self.containers[bag.index()].is_none() {
+            self.find_containers(bag);
+        }
+        let (graph, containers) = (&self.graph, self.containers[bag.index()].as_ref()?);
+        let mut names: Vec<&str> = graph
+            .ids()
+            .filter(|&id| containers[id.index() / 64] >> (id.index() % 64) & 1 == 1)
+            .map(|id| graph.node(id).as_str())
+            .collect();
+        names.sort_unstable();
+        Some(names)
+    }
+
+    /// Caches the containers of `bag` and of every bag that can hold it,
+    /// each one as the bags directly holding it plus their containers. If
+    /// some of those bags hold themselves, `bag` gets a search of its own.
+    fn find_containers(&mut self, bag: NodeId) {
+        let words = self.graph.len().div_ceil(64);
+        let cached = &self.containers;
+        let order = self
+            .graph
+            .ancestors_post_order(Some(bag), |ancestor| cached[ancestor.index()].is_some());
+        let order = match order {
+            Ok(order) => order,
+            Err(_) => {
+                let mut containers = vec![0; words];
+                for ancestor in self.graph.ancestors(bag) {
+                    insert(&mut containers, ancestor);
+                }
+                self.containers[bag.index()] = Some(containers);
+                return;
+            }
+        };
+        for bag in order {
+            let mut containers = vec![0; words];
+            for &(parent, _) in self.graph.predecessors(bag) {
+                insert(&mut containers, parent);
+                let inherited = self.containers[parent.index()].as_ref();
+                for (word, &bits) in containers.iter_mut().zip(inherited.into_iter().flatten()) {
+                    *word |= bits;
+                }
+            }
+            self.containers[bag.index()] = Some(containers);
+        }
+    }
+
+    /// How many bags `colour` must hold in total. Counting stops with an
+    /// error on rules that make a bag hold itself, or on a total too large
+    /// for a `u128`.
+    pub fn total_contents(&mut self, colour: &str) -> Result<u128, CountError> {
+        let start = self
+            .graph
+            .id(colour)
+            .ok_or_else(|| CountError::UnknownColour(String::from(colour)))?;
+        if let Some(total) = self.totals[start.index()] {
+            return Ok(total);
+        }
+
+        // Bags come after every bag inside them, so their children are
+        // always totalled first.
+        let totals = &self.totals;
+        let order = self
+            .graph
+            .post_order(Some(start), |bag| totals[bag.index()].is_some())
+            .map_err(CountError::Cycle)?;
+        for bag in order {
+            let total =
+                self.graph
+                    .successors(bag)
+                    .iter()
+                    .try_fold(0u128, |total, &(child, count)| {
+                        let inside = self.totals[child.index()]?.checked_add(1)?;
+                        total.checked_add(inside.checked_mul(count.into())?)
+                    });
+            let total = total.ok_or_else(|| CountError::Overflow {
+                colour: self.graph.node(bag).clone(),
+            })?;
+            self.totals[bag.index()] = Some(total);
+        }
+        Ok(self.totals[start.index()].unwrap())
+    }
+}
+
+/// One way a bag ends up inside another, listed from the outermost bag
+/// inwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainmentPath {
+    pub bags: Vec<String>,
+    /// How many of the innermost bag one outermost bag holds along this
+    /// path, or `None` if that does not fit in a `u64`.
+    pub quantity: Option<u64>,
+}
+
+impl fmt::Display for ContainmentPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.bags.join(" → "))?;
+        match self.quantity {
+            Some(quantity) => write!(f, " (×{})", quantity),
+            None => write!(f, " (too many to count)"),
+        }
+    }
+}
+
+/// Lists up to `limit` ways a bag can end up holding `target`, shortest
+/// first. Paths of the same length are ordered by colour, starting from
+/// the bag next to `target`. Paths never visit a bag twice, so cyclic
+/// rules still give a finite list.
+pub fn containment_paths(
+    rules: &HashMap<String, Bag>,
+    target: &str,
+    limit: usize,
+) -> Vec<ContainmentPath> {
+    let mut parents: HashMap<&str, Vec<(u32, &str)>> = HashMap::new();
+    for bag in rules.values() {
+        for (count, child) in &bag.children {
+            parents
+                .entry(child.as_str())
+                .or_default()
+                .push((*count, bag.description.as_str()));
+        }
+    }
+    for list in parents.values_mut() {
+        list.sort_unstable_by_key(|&(_, parent)| parent);
+    }
+
+    // Every partial path found while searching outwards from the target
+    // is itself a path, and a breadth-first search finds them shortest
+    // first.
+    let mut paths = Vec::new();
+    let mut queue: VecDeque<(Vec<&str>, Option<u64>)> = VecDeque::new();
+    queue.push_back((vec![target], Some(1)));
+    while let Some((path, quantity)) = queue.pop_front() {
+        let outermost = path[path.len() - 1];
+        for &(count, parent) in parents.get(outermost).map_or(&[][..], Vec::as_slice) {
+            if paths.len() == limit {
+                return paths;
+            }
+            if path.contains(&parent) {
+                continue;
+            }
+            let mut longer = path.clone();
+            longer.push(parent);
+            let quantity = quantity.and_then(|quantity| quantity.checked_mul(count.into()));
+            paths.push(ContainmentPath {
+                bags: longer.iter().rev().map(|&bag| String::from(bag)).collect(),
+                quantity,
+            });
+            queue.push_back((longer, quantity));
+        }
+    }
+    paths
+}
+
+/// Which part of a rule set to export. Exporting a scope that names an
+/// unknown colour gives `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope<'a> {
+    All,
+    /// The colour and every bag it eventually holds.
+    From(&'a str),
+    /// The colour and every bag that can eventually hold it.
+    To(&'a str),
+}
+
+/// The bags in `scope`, sorted by colour, or `None` if the scope names an
+/// unknown colour.
+fn select(graph: &Graph<String, u32>, scope: Scope) -> Option<Vec<NodeId>> {
+    let mut selected = match scope {
+        Scope::All => graph.ids().collect(),
+        Scope::From(colour) => {
+            let start = graph.id(colour)?;
+            let mut found = graph.reachable_from(start);
+            found.push(start);
+            found
+        }
+        Scope::To(colour) => {
+            let target = graph.id(colour)?;
+            let mut found = graph.ancestors(target);
+            found.push(target);
+            found
+        }
+    };
+    selected.sort_unstable_by_key(|&bag| graph.node(bag));
+    selected.dedup();
+    Some(selected)
+}
+
+/// The rules for the bags in `scope`, sorted by colour, keeping only the
+/// children that are also in scope.
+fn adjacency(rules: &HashMap<String, Bag>, scope: Scope) -> Option<Vec<Bag>> {
+    let graph = rule_graph(rules);
+    let selected = select(&graph, scope)?;
+    let mut included = vec![false; graph.len()];
+    for &bag in &selected {
+        included[bag.index()] = true;
+    }
+    let adjacency = selected
+        .iter()
+        .map(|&bag| {
+            let children = graph
+                .successors(bag)
+                .iter()
+                .filter(|(child, _)| included[child.index()])
+                .map(|&(child, count)| (count, graph.node(child).clone()))
+                .collect();
+            Bag {
+                description: graph.node(bag).clone(),
+                children,
+            }
+        })
+        .collect();
+    Some(adjacency)
+}
+
+/// Renders the rules in `scope` as a Graphviz digraph, with an edge from
+/// each bag to every bag it holds labelled by the count.
+pub fn to_dot(rules: &HashMap<String, Bag>, scope: Scope) -> Option<String> {
+    let quote = |name: &str| format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""));
+    let adjacency = adjacency(rules, scope)?;
+    let mut dot = String::from("digraph bags {\n");
+    for bag in &adjacency {
+        writeln!(dot, "    {};", quote(&bag.description)).unwrap();
+    }
+    for bag in &adjacency {
+        for (count, child) in &bag.children {
+            writeln!(
+                dot,
+                "    {} -> {} [label=\"{}\"];",
+                quote(&bag.description),
+                quote(child),
+                count
+            )
+            .unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    Some(dot)
+}
+
+#[derive(Serialize)]
+struct Edge<'a> {
+    colour: &'a str,
+    count: u32,
+}
+
+/// Renders the rules in `scope` as a JSON object mapping each colour to
+/// the bags it directly holds.
+pub fn to_json(rules: &HashMap<String, Bag>, scope: Scope) -> Option<String> {
+    let adjacency = adjacency(rules, scope)?;
+    let document: BTreeMap<&str, Vec<Edge>> = adjacency
+        .iter()
+        .map(|bag| {
+            let edges = bag
+                .children
+                .iter()
+                .map(|(count, colour)| Edge {
+                    colour,
+                    count: *count,
+                })
+                .collect();
+            (bag.description.as_str(), edges)
+        })
+        .collect();
+    Some(serde_json::to_string_pretty(&document).unwrap())
+}
+
+fn part_one(input: &HashMap<String, Bag>) -> usize {
+    BagQuery::new(input)
+        .containers(SHINY_GOLD)
+        .map_or(0, |containers| containers.len())
+}
+
+fn part_two(input: &HashMap<String, Bag>) -> Result<u128, CountError> {
+    match BagQuery::new(input).total_contents(SHINY_GOLD) {
+        Err(CountError::UnknownColour(_)) => Ok(0),
+        total => total,
+    }
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input = HashMap<String, Bag>;
+
+    fn parse(input: &str) -> Result<HashMap<String, Bag>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &HashMap<String, Bag>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &HashMap<String, Bag>) -> Solution {
+        Ok(Some(Box::new(part_two(input)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags."#;
+
+    #[test]
+    fn example() {
+        let parsed = parse_input(EXAMPLE).unwrap();
+        assert_eq!(part_one(&parsed), 4);
+        assert_eq!(part_two(&parsed), Ok(32));
+
+        let second = r#"shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags."#;
+        let second_parsed = parse_input(second).unwrap();
+        assert_eq!(part_two(&second_parsed), Ok(126));
+    }
+
+    #[test]
+    fn queries() {
+        let mut query = BagQuery::new(&parse_input(EXAMPLE).unwrap());
+        assert_eq!(
+            query.parents("shiny gold"),
+            Some(vec![(1, "bright white"), (2, "muted yellow")])
+        );
+        assert_eq!(
+            query.children("shiny gold"),
+            Some(vec![(1, "dark olive"), (2, "vibrant plum")])
+        );
+        assert_eq!(
+            query.containers("bright white"),
+            Some(vec!["dark orange", "light red"])
+        );
+        assert_eq!(query.containers("light red"), Some(vec![]));
+        assert_eq!(query.total_contents("dark olive"), Ok(7));
+        assert_eq!(query.total_contents("light red"), Ok(186));
+        assert_eq!(query.total_contents("faded blue"), Ok(0));
+        assert_eq!(
+            query.total_contents("plaid mauve"),
+            Err(CountError::UnknownColour(String::from("plaid mauve")))
+        );
+        assert_eq!(query.parents("plaid mauve"), None);
+
+        let rules = parse_input(EXAMPLE).unwrap();
+        let graph = rule_graph(&rules);
+        for colour in rules.keys() {
+            let bag = graph.id(colour.as_str()).unwrap();
+            let mut expected: Vec<&str> = graph
+                .ancestors(bag)
+                .into_iter()
+                .map(|ancestor| graph.node(ancestor).as_str())
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(query.containers(colour), Some(expected));
+        }
+    }
+
+    #[test]
+    fn paths() {
+        let rules = parse_input(EXAMPLE).unwrap();
+        let paths: Vec<String> = containment_paths(&rules, SHINY_GOLD, 10)
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "bright white → shiny gold (×1)",
+                "muted yellow → shiny gold (×2)",
+                "dark orange → bright white → shiny gold (×3)",
+                "light red → bright white → shiny gold (×1)",
+                "dark orange → muted yellow → shiny gold (×8)",
+                "light red → muted yellow → shiny gold (×4)",
+            ]
+        );
+
+        let capped = containment_paths(&rules, SHINY_GOLD, 3);
+        assert_eq!(capped.len(), 3);
+        assert_eq!(
+            capped[2].bags,
+            vec!["dark orange", "bright white", "shiny gold"]
+        );
+        assert_eq!(capped[2].quantity, Some(3));
+        assert!(containment_paths(&rules, "light red", 10).is_empty());
+
+        // Muted yellow and faded blue hold each other, yet every path ends
+        // and none of them goes around the loop.
+        let cyclic = r#"light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+faded blue bags contain 2 muted yellow bags."#;
+        let rules = parse_input(cyclic).unwrap();
+        let paths: Vec<String> = containment_paths(&rules, SHINY_GOLD, 100)
+            .iter()
+            .map(|path| path.to_string())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "bright white → shiny gold (×1)",
+                "muted yellow → shiny gold (×2)",
+                "dark orange → bright white → shiny gold (×3)",
+                "light red → bright white → shiny gold (×1)",
+                "dark orange → muted yellow → shiny gold (×8)",
+                "faded blue → muted yellow → shiny gold (×4)",
+                "light red → muted yellow → shiny gold (×4)",
+            ]
+        );
+    }
+
+    #[test]
+    fn export() {
+        let rules = parse_input(EXAMPLE).unwrap();
+        assert_eq!(
+            to_dot(&rules, Scope::To("bright white")).unwrap(),
+            "digraph bags {\n    \"bright white\";\n    \"dark orange\";\n    \
+             \"light red\";\n    \"dark orange\" -> \"bright white\" [label=\"3\"];\n    \
+             \"light red\" -> \"bright white\" [label=\"1\"];\n}\n"
+        );
+        let dot = to_dot(&rules, Scope::All).unwrap();
+        assert_eq!(dot.matches(" -> ").count(), 13);
+        assert!(dot.contains("\"muted yellow\" -> \"faded blue\" [label=\"9\"];"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json(&rules, Scope::From("vibrant plum")).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "dotted black": [],
+                "faded blue": [],
+                "vibrant plum": [
+                    {"colour": "faded blue", "count": 5},
+                    {"colour": "dotted black", "count": 6}
+                ]
+            })
+        );
+        assert_eq!(to_json(&rules, Scope::From("plaid mauve")), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = "pale dusky turquoise bags contain 1 red bag, 12 very shiny gold bags.\n\
+                     red bags contain no other bags.\n\
+                     very shiny gold bags contain 3 red bags.";
+        let rules = parse_input(input).unwrap();
+        assert_eq!(
+            rules["pale dusky turquoise"].children,
+            vec![
+                (1, String::from("red")),
+                (12, String::from("very shiny gold"))
+            ]
+        );
+        let serialized = serialize(&rules);
+        assert_eq!(
+            serialized,
+            "pale dusky turquoise bags contain 1 red bag, 12 very shiny gold bags.\n\
+             red bags contain no other bags.\n\
+             very shiny gold bags contain 3 red bags.\n"
+        );
+        assert_eq!(parse_input(&serialized).unwrap(), rules);
+    }
+
+    #[test]
+    fn hostile_rules() {
+        let input = "red bags contain 1 blue bag.\n\
+                     blue bags contain 2 green bags, 1 dark grey bag.\n\
+                     green bags contain no other bags.\n\
+                     dark grey bags contain 3 red bags.";
+        let mut query = BagQuery::new(&parse_input(input).unwrap());
+        assert_eq!(
+            query.containers("green"),
+            Some(vec!["blue", "dark grey", "red"])
+        );
+        assert_eq!(query.total_contents("green"), Ok(0));
+        let error = query.total_contents("red").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "cycle detected: red -> blue -> dark grey -> red"
+        );
+        let mut query = BagQuery::new(&parse_input("red bags contain 1 red bag.").unwrap());
+        assert_eq!(
+            query.total_contents("red").unwrap_err().to_string(),
+            "cycle detected: red -> red"
+        );
+        let cyclic = "shiny gold bags contain 1 blue bag.\n\
+                      blue bags contain 1 shiny gold bag.";
+        assert_eq!(
+            part_two(&parse_input(cyclic).unwrap())
+                .unwrap_err()
+                .to_string(),
+            "cycle detected: shiny gold -> blue -> shiny gold"
+        );
+
+        // Each tier holds 4294967295 bags of the next one, so the total
+        // no longer fits in a u128 five tiers above the innermost.
+        let tiers = ["a", "b", "c", "d", "e", "f", "g"];
+        let mut rules: Vec<String> = tiers
+            .windows(2)
+            .map(|pair| {
+                format!(
+                    "tier {} bags contain 4294967295 tier {} bags.",
+                    pair[0], pair[1]
+                )
+            })
+            .collect();
+        rules.push(String::from("tier g bags contain no other bags."));
+        let mut query = BagQuery::new(&parse_input(&rules.join("\n")).unwrap());
+        assert!(query.total_contents("tier d").unwrap() > u128::from(u64::MAX));
+        assert_eq!(
+            query.total_contents("tier a"),
+            Err(CountError::Overflow {
+                colour: String::from("tier b")
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        let errors = [
+            ("red bag contain no other bags.", 5, "\"bags\""),
+            ("red bags contains no other bags.", 9, "\" contain \""),
+            ("red bags contain 2 blue bag.", 25, "\"bags\""),
+            ("red bags contain 1 blue bags.", 25, "\"bag\""),
+            ("red bags contain 0 blue bags.", 18, "a bag count"),
+            ("red bags contain 2 blue.", 24, "\" bags\""),
+            ("red bags contain 2 bags.", 20, "a colour"),
+            ("red bags contain no other bags", 31, "\".\""),
+            (
+                "red bags contain no other bags.!",
+                32,
+                "the end of the line",
+            ),
+            ("Red bags contain no other bags.", 1, "a colour"),
+        ];
+        for &(rule, column, expected) in errors.iter() {
+            let error = parse_input(rule).unwrap_err();
+            assert_eq!(
+                (error.column, error.expected.as_str()),
+                (column, expected),
+                "{}",
+                rule
+            );
+        }
+        let error = parse_input("red bags contain no other bags.\nred bags contain 1 red bag.")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 7: line 2, column 1: expected a single rule for red"
+        );
+
+        let input = "bright white bags contain 1 shiny gold bag.\n\
+                     muted yellow bags contain 2 shiny gold bags, nine faded blue bags.";
+        let error = parse_input(input).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 7: line 2, column 46: expected a bag count"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::console::{Halt, Instruction, Machine};
+use crate::error::{lines, Line, ParseError};
+use crate::solver::{Solution, Solver};
+
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    lines(8, input)?.iter().map(parse_line).collect()
+}
+
+fn parse_line(line: &Line) -> Result<Instruction, ParseError> {
+    let (operation, arg) = line
+        .text
+        .split_once(' ')
+        .ok_or_else(|| line.error_at(line.text.len() + 1, "an argument"))?;
+    let parsed = line.parse::<i64>(arg, "a signed integer")?;
+    match operation {
+        "nop" => Ok(Instruction::Nop(parsed)),
+        "acc" => Ok(Instruction::Acc(parsed)),
+        "jmp" => Ok(Instruction::Jmp(parsed)),
+        _ => Err(line.error(operation, "one of nop, acc, jmp")),
+    }
+}
+
+fn part_one(input: &[Instruction]) -> i64 {
+    Machine::new(input).run().accumulator()
+}
+
+/// Exactly one `jmp` or `nop` is corrupted. Only instructions that run
+/// before the loop is detected can be the culprit, so try repairing each
+/// of those in turn. Returns `None` if no single repair makes the program
+/// terminate.
+fn part_two(input: &[Instruction]) -> Option<i64> {
+    let mut original = Machine::new(input);
+    if let Halt::Terminated { accumulator } = original.run() {
+        return Some(accumulator);
+    }
+
+    for index in original.executed() {
+        if let Some(repaired) = input[index].repaired() {
+            if let Halt::Terminated { accumulator } =
+                Machine::new(input).patched(index, repaired).run()
+            {
+                return Some(accumulator);
+            }
+        }
+    }
+    None
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<Instruction>) -> Solution {
+        Ok(Some(Box::new(part_one(input))))
+    }
+
+    fn part2(input: &Vec<Instruction>) -> Solution {
+        let accumulator = part_two(input).ok_or("no single repair makes the program terminate")?;
+        Ok(Some(Box::new(accumulator)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6"#;
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(part_one(&parsed), 5);
+        assert_eq!(part_two(&parsed), Some(8));
+        let parsed = parse_input("acc +1\njmp -1\nacc +1\njmp -1").unwrap();
+        assert_eq!(part_two(&parsed), None);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input("nop +0\nacc +1\nmov +4").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "day 8: line 3, column 1: expected one of nop, acc, jmp"
+        );
+
+        let error = parse_input("nop +0\njmp four").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 5));
+    }
+}
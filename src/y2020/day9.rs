@@ -0,0 +1,340 @@
+use crate::error::{lines, ParseError};
+use crate::solver::{Solution, Solver};
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead};
+
+fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    lines(9, input)?
+        .iter()
+        .map(|line| line.parse::<u64>(line.text, "an unsigned integer"))
+        .collect()
+}
+
+/// How many numbers precede each one in the puzzle's XMAS data.
+pub const PREAMBLE: usize = 25;
+
+/// The last `len` numbers of an XMAS stream, along with the sums of every
+/// pair of them. Pushing a number only touches the pairs it enters or
+/// leaves, so sliding the window costs O(len) rather than O(len²).
+#[derive(Debug, Clone)]
+pub struct Window {
+    len: usize,
+    numbers: VecDeque<u64>,
+    sums: HashMap<u64, usize>,
+}
+
+impl Window {
+    pub fn new(len: usize) -> Window {
+        Window {
+            len,
+            numbers: VecDeque::new(),
+            sums: HashMap::new(),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.numbers.len() == self.len
+    }
+
+    /// Whether two different numbers in the window add up to `number`.
+    pub fn is_valid(&self, number: u64) -> bool {
+        self.sums.contains_key(&number)
+    }
+
+    /// Adds `number` to the window, dropping the oldest one if it is full.
+    pub fn push(&mut self, number: u64) {
+        if self.len == 0 {
+            return;
+        }
+        if self.is_full() {
+            let oldest = self.numbers.pop_front().unwrap();
+            for &other in &self.numbers {
+                if let Some(sum) = oldest.checked_add(other) {
+                    let count = self.sums.get_mut(&sum).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        self.sums.remove(&sum);
+                    }
+                }
+            }
+        }
+        for &other in &self.numbers {
+            if let Some(sum) = number.checked_add(other) {
+                *self.sums.entry(sum).or_insert(0) += 1;
+            }
+        }
+        self.numbers.push_back(number);
+    }
+}
+
+/// A number that is not the sum of two of the numbers before it, and its
+/// position in the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Invalid {
+    pub index: usize,
+    pub number: u64,
+}
+
+/// Checks an XMAS stream one number at a time, keeping only the preamble
+/// in memory.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    window: Window,
+    index: usize,
+}
+
+impl Validator {
+    pub fn new(preamble: usize) -> Validator {
+        Validator {
+            window: Window::new(preamble),
+            index: 0,
+        }
+    }
+
+    /// Feeds the next number, returning it if it is invalid. Numbers in the
+    /// preamble are always valid.
+    pub fn push(&mut self, number: u64) -> Option<Invalid> {
+        let invalid = if self.window.is_full() && !self.window.is_valid(number) {
+            Some(Invalid {
+                index: self.index,
+                number,
+            })
+        } else {
+            None
+        };
+        self.window.push(number);
+        self.index += 1;
+        invalid
+    }
+}
+
+/// Lazily yields every invalid number in `numbers`.
+pub fn invalid_numbers<I>(numbers: I, preamble: usize) -> impl Iterator<Item = Invalid>
+where
+    I: IntoIterator<Item = u64>,
+{
+    let mut validator = Validator::new(preamble);
+    numbers
+        .into_iter()
+        .filter_map(move |number| validator.push(number))
+}
+
+/// Reads one number per line, so streams too big to load can be checked.
+/// Lines that are not numbers are reported as `InvalidData` errors.
+pub fn read_numbers<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<u64>> {
+    reader.lines().enumerate().map(|(index, line)| {
+        let line = line?;
+        let text = line.trim_end();
+        text.parse::<u64>().map_err(|_| {
+            let error = ParseError::new(9, index + 1, 1, "an unsigned integer");
+            io::Error::new(io::ErrorKind::InvalidData, error)
+        })
+    })
+}
+
+pub fn first_invalid(input: &[u64], preamble: usize) -> Option<u64> {
+    invalid_numbers(input.iter().copied(), preamble)
+        .next()
+        .map(|invalid| invalid.number)
+}
+
+/// A contiguous run of at least two numbers adding up to a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weakness {
+    pub start: usize,
+    pub len: usize,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Weakness {
+    /// The sum of the smallest and largest numbers, widened so that it
+    /// cannot overflow.
+    pub fn value(&self) -> u128 {
+        u128::from(self.min) + u128::from(self.max)
+    }
+}
+
+/// Finds the first run of at least two numbers that adds up to `target`
+/// in a single two-pointer pass. Only the current run is buffered, and
+/// runs longer than `capacity` numbers are not considered.
+pub fn find_weakness<I>(numbers: I, target: u64, capacity: usize) -> Option<Weakness>
+where
+    I: IntoIterator<Item = u64>,
+{
+    let mut run = VecDeque::new();
+    let mut sum: u128 = 0;
+    let target = u128::from(target);
+
+    for (index, number) in numbers.into_iter().enumerate() {
+        run.push_back(number);
+        sum += u128::from(number);
+        while sum > target || run.len() > capacity {
+            sum -= u128::from(run.pop_front()?);
+        }
+        if sum == target && run.len() >= 2 {
+            return Some(Weakness {
+                start: index + 1 - run.len(),
+                len: run.len(),
+                min: *run.iter().min()?,
+                max: *run.iter().max()?,
+            });
+        }
+    }
+    None
+}
+
+/// Adds the smallest and largest numbers of the contiguous run that sums
+/// to the first invalid number.
+pub fn encryption_weakness(input: &[u64], preamble: usize) -> Option<u128> {
+    let target = first_invalid(input, preamble)?;
+    find_weakness(input.iter().copied(), target, input.len()).map(|weakness| weakness.value())
+}
+
+pub fn part_one(input: &[u64]) -> Option<u64> {
+    first_invalid(input, PREAMBLE)
+}
+
+pub fn part_two(input: &[u64]) -> Option<u128> {
+    encryption_weakness(input, PREAMBLE)
+}
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Vec<u64>) -> Solution {
+        let invalid = part_one(input).ok_or("every number is valid")?;
+        Ok(Some(Box::new(invalid)))
+    }
+
+    fn part2(input: &Vec<u64>) -> Solution {
+        let weakness = part_two(input).ok_or("no encryption weakness found")?;
+        Ok(Some(Box::new(weakness)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = r#"35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576"#;
+        let parsed = parse_input(input).unwrap();
+        assert_eq!(first_invalid(&parsed, 5), Some(127));
+        assert_eq!(encryption_weakness(&parsed, 5), Some(62));
+        assert_eq!(first_invalid(&parsed[..14], 5), None);
+    }
+
+    #[test]
+    fn streaming() {
+        let reader = io::Cursor::new(
+            "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n\
+                                      117\n150\n182\n127\n219\n299\n277\n309\n576\n",
+        );
+        let numbers: Vec<u64> = read_numbers(reader).map(Result::unwrap).collect();
+        let invalid: Vec<Invalid> = invalid_numbers(numbers.iter().copied(), 5).collect();
+        assert_eq!(
+            invalid,
+            vec![Invalid {
+                index: 14,
+                number: 127
+            }]
+        );
+        assert_eq!(
+            find_weakness(numbers.iter().copied(), 127, 4),
+            Some(Weakness {
+                start: 2,
+                len: 4,
+                min: 15,
+                max: 47
+            })
+        );
+        assert_eq!(find_weakness(numbers.iter().copied(), 127, 3), None);
+        assert_eq!(find_weakness(vec![127, 1], 127, 10), None);
+        let weakness = find_weakness(vec![1, u64::MAX - 1], u64::MAX, usize::MAX).unwrap();
+        assert_eq!(weakness.value(), u128::from(u64::MAX));
+        let weakness = Weakness {
+            start: 0,
+            len: 2,
+            min: u64::MAX,
+            max: u64::MAX,
+        };
+        assert_eq!(weakness.value(), 2 * u128::from(u64::MAX));
+        assert_eq!(
+            find_weakness(vec![u64::MAX, u64::MAX, 1, 2], 3, 10)
+                .unwrap()
+                .start,
+            2
+        );
+
+        let mut errors = read_numbers(io::Cursor::new("1\nx\n"));
+        assert_eq!(errors.next().unwrap().unwrap(), 1);
+        let error = errors.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.to_string(),
+            "day 9: line 2, column 1: expected an unsigned integer"
+        );
+    }
+
+    #[test]
+    fn sliding_window() {
+        let mut unbounded = Validator::new(usize::MAX);
+        assert_eq!(unbounded.push(1), None);
+        assert_eq!(part_one(&[1, 2, 3]), None);
+
+        let mut window = Window::new(3);
+        for number in 1..=3 {
+            window.push(number);
+        }
+        assert!(window.is_full());
+        assert!(window.is_valid(3) && window.is_valid(5));
+        assert!(!window.is_valid(2) && !window.is_valid(6));
+        window.push(3);
+        assert!(!window.is_valid(3));
+        assert!(window.is_valid(6));
+        window.push(u64::MAX);
+        assert!(window.is_valid(6));
+        assert!(!window.is_valid(5));
+    }
+
+    #[test]
+    fn invalid_input() {
+        let error = parse_input(
+            "35
+-20
+",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 9: line 2, column 1: expected an unsigned integer"
+        );
+    }
+}
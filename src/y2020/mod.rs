@@ -0,0 +1,30 @@
+use crate::solver::Day;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+
+pub const YEAR: u16 = 2020;
+
+/// Every solved day of 2020.
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::new::<day1::Day1>(YEAR, 1),
+        Day::new::<day2::Day2>(YEAR, 2),
+        Day::new::<day3::Day3>(YEAR, 3),
+        Day::new::<day4::Day4>(YEAR, 4),
+        Day::new::<day5::Day5>(YEAR, 5),
+        Day::new::<day6::Day6>(YEAR, 6),
+        Day::new::<day7::Day7>(YEAR, 7),
+        Day::new::<day8::Day8>(YEAR, 8),
+        Day::new::<day9::Day9>(YEAR, 9),
+        Day::new::<day10::Day10>(YEAR, 10),
+    ]
+}